@@ -298,7 +298,8 @@ impl_stable_hash_for!(enum token::Lit {
     Str_(val),
     ByteStr(val),
     StrRaw(val, n),
-    ByteStrRaw(val, n)
+    ByteStrRaw(val, n),
+    Frac(numerator, denominator)
 });
 
 fn hash_token<'a, 'gcx, W: StableHasherResult>(
@@ -357,7 +358,10 @@ fn hash_token<'a, 'gcx, W: StableHasherResult>(
             ident.name.hash_stable(hcx, hasher);
             is_raw.hash_stable(hcx, hasher);
         }
-        token::Token::Lifetime(ident) => ident.name.hash_stable(hcx, hasher),
+        token::Token::Lifetime(ident, is_raw) => {
+            ident.name.hash_stable(hcx, hasher);
+            is_raw.hash_stable(hcx, hasher);
+        }
 
         token::Token::Interpolated(_) => {
             bug!("interpolated tokens should not be present in the HIR")
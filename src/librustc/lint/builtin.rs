@@ -365,6 +365,60 @@ pub mod parser {
         Warn,
         "ill-formed attribute inputs that were previously accepted and used in practice"
     }
+
+    declare_lint! {
+        pub TRAILING_WHITESPACE,
+        Allow,
+        "detects whitespace at the end of a line"
+    }
+
+    declare_lint! {
+        pub INTEGER_LITERAL_METHOD_CALL,
+        Allow,
+        "detects method calls and field accesses on integer literals, like `1.foo()`"
+    }
+
+    declare_lint! {
+        pub MISSING_FINAL_NEWLINE,
+        Allow,
+        "detects source files that don't end with a newline"
+    }
+
+    declare_lint! {
+        pub C_STYLE_OCTAL_LITERALS,
+        Allow,
+        "detects decimal literals with a leading zero, which C reads as octal"
+    }
+
+    declare_lint! {
+        pub UNNECESSARY_RAW_STRINGS,
+        Allow,
+        "detects raw string literals that could be normal string literals"
+    }
+
+    declare_lint! {
+        pub TABS_IN_DOC_COMMENTS,
+        Allow,
+        "detects tab characters in doc comments"
+    }
+
+    declare_lint! {
+        pub MIXED_INDENTATION_IN_RAW_STRINGS,
+        Allow,
+        "detects lines of raw strings indented with both tabs and spaces"
+    }
+
+    declare_lint! {
+        pub MIXED_LINE_ENDINGS,
+        Allow,
+        "detects source files with both LF and CRLF line endings"
+    }
+
+    declare_lint! {
+        pub ODD_QUOTES_ON_LINE,
+        Allow,
+        "detects lines with an odd number of string literal quotes"
+    }
 }
 
 declare_lint! {
@@ -453,6 +507,15 @@ declare_lint_pass! {
         MACRO_EXPANDED_MACRO_EXPORTS_ACCESSED_BY_ABSOLUTE_PATHS,
         parser::QUESTION_MARK_MACRO_SEP,
         parser::ILL_FORMED_ATTRIBUTE_INPUT,
+        parser::TRAILING_WHITESPACE,
+        parser::INTEGER_LITERAL_METHOD_CALL,
+        parser::MISSING_FINAL_NEWLINE,
+        parser::C_STYLE_OCTAL_LITERALS,
+        parser::UNNECESSARY_RAW_STRINGS,
+        parser::TABS_IN_DOC_COMMENTS,
+        parser::MIXED_INDENTATION_IN_RAW_STRINGS,
+        parser::MIXED_LINE_ENDINGS,
+        parser::ODD_QUOTES_ON_LINE,
         DEPRECATED_IN_FUTURE,
         AMBIGUOUS_ASSOCIATED_ITEMS,
         NESTED_IMPL_TRAIT,
@@ -27,7 +27,12 @@ use crate::hir::def_id::{CrateNum, LOCAL_CRATE};
 use crate::hir::intravisit;
 use crate::hir;
 use crate::lint::builtin::BuiltinLintDiagnostics;
-use crate::lint::builtin::parser::{QUESTION_MARK_MACRO_SEP, ILL_FORMED_ATTRIBUTE_INPUT};
+use crate::lint::builtin::parser::{
+    QUESTION_MARK_MACRO_SEP, ILL_FORMED_ATTRIBUTE_INPUT, TRAILING_WHITESPACE,
+    INTEGER_LITERAL_METHOD_CALL, MISSING_FINAL_NEWLINE, C_STYLE_OCTAL_LITERALS,
+    UNNECESSARY_RAW_STRINGS, TABS_IN_DOC_COMMENTS, MIXED_INDENTATION_IN_RAW_STRINGS,
+    MIXED_LINE_ENDINGS, ODD_QUOTES_ON_LINE,
+};
 use crate::session::{Session, DiagnosticMessageId};
 use crate::ty::TyCtxt;
 use crate::ty::query::Providers;
@@ -82,6 +87,17 @@ impl Lint {
         match lint_id {
             BufferedEarlyLintId::QuestionMarkMacroSep => QUESTION_MARK_MACRO_SEP,
             BufferedEarlyLintId::IllFormedAttributeInput => ILL_FORMED_ATTRIBUTE_INPUT,
+            BufferedEarlyLintId::TrailingWhitespace => TRAILING_WHITESPACE,
+            BufferedEarlyLintId::IntegerLiteralMethodCall => INTEGER_LITERAL_METHOD_CALL,
+            BufferedEarlyLintId::MissingFinalNewline => MISSING_FINAL_NEWLINE,
+            BufferedEarlyLintId::CStyleOctalLiterals => C_STYLE_OCTAL_LITERALS,
+            BufferedEarlyLintId::UnnecessaryRawStrings => UNNECESSARY_RAW_STRINGS,
+            BufferedEarlyLintId::TabsInDocComments => TABS_IN_DOC_COMMENTS,
+            BufferedEarlyLintId::MixedIndentationInRawStrings => {
+                MIXED_INDENTATION_IN_RAW_STRINGS
+            }
+            BufferedEarlyLintId::MixedLineEndings => MIXED_LINE_ENDINGS,
+            BufferedEarlyLintId::OddQuotesOnLine => ODD_QUOTES_ON_LINE,
         }
     }
 
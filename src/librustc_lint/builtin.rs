@@ -87,6 +87,79 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for WhileTrue {
     }
 }
 
+declare_lint! {
+    NEVER_LOOP,
+    Allow,
+    "detects loops whose body unconditionally breaks out on the first iteration"
+}
+
+declare_lint_pass!(NeverLoop => [NEVER_LOOP]);
+
+impl NeverLoop {
+    /// Returns the span of the `break` at the top level of `body` that leaves the
+    /// loop `loop_id` during its first iteration, unless a `continue` may be
+    /// reached before it.
+    fn unconditional_break(loop_id: hir::HirId, body: &hir::Block) -> Option<Span> {
+        use rustc::hir::intravisit::{self, Visitor};
+        struct FindContinue {
+            found: bool,
+        }
+        impl<'v> Visitor<'v> for FindContinue {
+            fn nested_visit_map<'this>(&'this mut self) -> intravisit::NestedVisitorMap<'this, 'v>
+            {
+                intravisit::NestedVisitorMap::None
+            }
+
+            fn visit_expr(&mut self, e: &'v hir::Expr) {
+                if let hir::ExprKind::Continue(..) = e.node {
+                    self.found = true;
+                }
+                intravisit::walk_expr(self, e)
+            }
+        }
+
+        let breaks_loop = |e: &hir::Expr| match e.node {
+            hir::ExprKind::Break(ref dest, _) => dest.target_id.ok() == Some(loop_id),
+            _ => false,
+        };
+
+        let mut visitor = FindContinue { found: false };
+        for stmt in &body.stmts {
+            match stmt.node {
+                hir::StmtKind::Expr(ref e) |
+                hir::StmtKind::Semi(ref e) if breaks_loop(e) => {
+                    return if visitor.found { None } else { Some(e.span) };
+                }
+                _ => visitor.visit_stmt(stmt),
+            }
+        }
+        match body.expr {
+            Some(ref e) if breaks_loop(e) && !visitor.found => Some(e.span),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NeverLoop {
+    fn check_expr(&mut self, cx: &LateContext<'_, '_>, e: &hir::Expr) {
+        let body = match e.node {
+            hir::ExprKind::While(_, ref body, _) |
+            hir::ExprKind::Loop(ref body, _, hir::LoopSource::Loop) => body,
+            _ => return,
+        };
+        if e.span.ctxt() != SyntaxContext::empty() {
+            return;
+        }
+        if let Some(break_span) = NeverLoop::unconditional_break(e.hir_id, body) {
+            let loop_span = cx.tcx.sess.source_map().def_span(e.span);
+            let mut err = cx.struct_span_lint(NEVER_LOOP, loop_span, "this loop runs at most once");
+            err.span_label(break_span, "this `break` is always reached on the first iteration");
+            err.help("use an `if` or straight-line code instead of a loop");
+            err.emit();
+        }
+    }
+}
+
 declare_lint! {
     BOX_POINTERS,
     Allow,
@@ -1248,11 +1321,73 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TrivialConstraints {
     }
 }
 
+declare_lint! {
+    UNUSED_TYPE_PARAMETERS,
+    Allow,
+    "detects type parameters of functions that are used in neither the signature nor the body"
+}
+
+declare_lint_pass!(UnusedTypeParameters => [UNUSED_TYPE_PARAMETERS]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedTypeParameters {
+    fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::Item) {
+        use rustc::hir::intravisit::{self, Visitor};
+        use rustc::hir::map::Map;
+        struct CollectTyParams<'a, 'tcx: 'a> {
+            hir_map: &'a Map<'tcx>,
+            used: FxHashSet<DefId>,
+        }
+        impl<'a, 'tcx> Visitor<'tcx> for CollectTyParams<'a, 'tcx> {
+            fn nested_visit_map<'this>(&'this mut self)
+                                       -> intravisit::NestedVisitorMap<'this, 'tcx> {
+                intravisit::NestedVisitorMap::OnlyBodies(self.hir_map)
+            }
+
+            fn visit_path(&mut self, path: &'tcx hir::Path, _: hir::HirId) {
+                if let Res::Def(DefKind::TyParam, def_id) = path.res {
+                    self.used.insert(def_id);
+                }
+                intravisit::walk_path(self, path)
+            }
+
+            fn visit_ty(&mut self, ty: &'tcx hir::Ty) {
+                // `impl Trait` return types, including those of `async fn`s,
+                // are separate existential items using the function's parameters
+                if let hir::TyKind::Def(item_id, _) = ty.node {
+                    let item = self.hir_map.expect_item_by_hir_id(item_id.id);
+                    intravisit::walk_item(self, item);
+                }
+                intravisit::walk_ty(self, ty)
+            }
+        }
+
+        let generics = match item.node {
+            hir::ItemKind::Fn(.., ref generics, _) => generics,
+            _ => return,
+        };
+        let mut visitor = CollectTyParams { hir_map: cx.tcx.hir(), used: FxHashSet::default() };
+        intravisit::walk_item(&mut visitor, item);
+        for param in &generics.params {
+            // `impl Trait` arguments are always used by their argument.
+            if let GenericParamKind::Type { synthetic: None, .. } = param.kind {
+                let def_id = cx.tcx.hir().local_def_id_from_hir_id(param.hir_id);
+                if !visitor.used.contains(&def_id) {
+                    let msg = format!("type parameter `{}` is never used", param.name.ident());
+                    let mut err = cx.struct_span_lint(UNUSED_TYPE_PARAMETERS, param.span, &msg);
+                    err.help("remove it, or use it in the function's signature or body");
+                    err.emit();
+                }
+            }
+        }
+    }
+}
+
 declare_lint_pass!(
     /// Does nothing as a lint pass, but registers some `Lint`s
     /// which are used by other parts of the compiler.
     SoftLints => [
         WHILE_TRUE,
+        NEVER_LOOP,
         BOX_POINTERS,
         NON_SHORTHAND_FIELD_PATTERNS,
         UNSAFE_CODE,
@@ -1269,7 +1404,8 @@ declare_lint_pass!(
         UNIONS_WITH_DROP_FIELDS,
         UNREACHABLE_PUB,
         TYPE_ALIAS_BOUNDS,
-        TRIVIAL_BOUNDS
+        TRIVIAL_BOUNDS,
+        UNUSED_TYPE_PARAMETERS
     ]
 );
 
@@ -142,6 +142,7 @@ macro_rules! late_lint_mod_passes {
         $macro!($args, [
             HardwiredLints: HardwiredLints,
             WhileTrue: WhileTrue,
+            NeverLoop: NeverLoop,
             ImproperCTypes: ImproperCTypes,
             VariantSizeDifferences: VariantSizeDifferences,
             BoxPointers: BoxPointers,
@@ -168,6 +169,7 @@ macro_rules! late_lint_mod_passes {
             TypeAliasBounds: TypeAliasBounds,
 
             TrivialConstraints: TrivialConstraints,
+            UnusedTypeParameters: UnusedTypeParameters,
             TypeLimits: TypeLimits::new(),
 
             NonSnakeCase: NonSnakeCase,
@@ -176,17 +176,23 @@ impl<'a> AstValidator<'a> {
         &self.session.diagnostic()
     }
 
+    /// Whether `ident` was written in raw form, like `'r#fn`.
+    fn is_raw(&self, ident: Ident) -> bool {
+        self.session.parse_sess.raw_identifier_spans.borrow().contains(&ident.span)
+    }
+
     fn check_lifetime(&self, ident: Ident) {
         let valid_names = [keywords::UnderscoreLifetime.name(),
                            keywords::StaticLifetime.name(),
                            keywords::Invalid.name()];
-        if !valid_names.contains(&ident.name) && ident.without_first_quote().is_reserved() {
+        if !valid_names.contains(&ident.name) && ident.without_first_quote().is_reserved() &&
+           !self.is_raw(ident) {
             self.err_handler().span_err(ident.span, "lifetimes cannot use keyword names");
         }
     }
 
     fn check_label(&self, ident: Ident) {
-        if ident.without_first_quote().is_reserved() {
+        if ident.without_first_quote().is_reserved() && !self.is_raw(ident) {
             self.err_handler()
                 .span_err(ident.span, &format!("invalid label name `{}`", ident.name));
         }
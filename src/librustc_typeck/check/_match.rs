@@ -1257,6 +1257,19 @@ https://doc.rust-lang.org/reference/types.html#trait-objects");
 
                         // we don't want to throw `E0027` in case we have thrown `E0026` for them
                         unmentioned_fields.retain(|&x| x.as_str() != suggested_name.as_str());
+                    } else if unmentioned_fields.len() == 1 && !etc &&
+                              variant.ctor_kind == CtorKind::Fictive {
+                        // The only field left out is the likely target of the typo, even if
+                        // the names are too far apart for the similar name lookup.
+                        let field = unmentioned_fields.remove(0);
+                        err.span_suggestion_short(
+                            ident.span,
+                            &format!("`{}` has a field named `{}`",
+                                     tcx.def_path_str(variant.def_id),
+                                     field),
+                            field.to_string(),
+                            Applicability::MaybeIncorrect,
+                        );
                     }
                 }
             }
@@ -34,6 +34,7 @@ use errors::{DiagnosticBuilder,Applicability};
 use crate::hir::def_id::DefId;
 use crate::lint;
 use rustc::hir;
+use rustc::hir::def::CtorKind;
 use rustc::session::Session;
 use rustc::traits;
 use rustc::ty::{self, Ty, TypeFoldable, TypeAndMut};
@@ -42,6 +43,7 @@ use rustc::ty::adjustment::AllowTwoPhase;
 use rustc::ty::cast::{CastKind, CastTy};
 use rustc::middle::lang_items;
 use syntax::ast;
+use syntax::util::parser::PREC_POSTFIX;
 use syntax_pos::Span;
 use crate::util::common::ErrorReported;
 
@@ -170,6 +172,11 @@ fn make_invalid_casting_error<'a, 'gcx, 'tcx>(sess: &'a Session,
                        fcx.ty_to_string(cast_ty))
 }
 
+/// Whether `ty` is an integer or float type, including `{integer}` and `{float}`.
+fn is_number(ty: Ty<'_>) -> bool {
+    ty.is_integral() || ty.is_floating_point()
+}
+
 impl<'a, 'gcx, 'tcx> CastCheck<'tcx> {
     pub fn new(fcx: &FnCtxt<'a, 'gcx, 'tcx>,
                expr: &'tcx hir::Expr,
@@ -284,15 +291,27 @@ impl<'a, 'gcx, 'tcx> CastCheck<'tcx> {
                 type_error_struct!(fcx.tcx.sess, self.span, self.expr_ty, E0604,
                     "only `u8` can be cast as `char`, not `{}`", self.expr_ty).emit();
             }
-            CastError::NonScalar => {
+            CastError::NonScalar if self.expr_ty.is_enum() && self.cast_ty.is_enum() => {
                 type_error_struct!(fcx.tcx.sess, self.span, self.expr_ty, E0605,
-                                   "non-primitive cast: `{}` as `{}`",
+                                   "cannot cast between enum types: `{}` as `{}`",
                                    self.expr_ty,
                                    fcx.ty_to_string(self.cast_ty))
-                                  .note("an `as` expression can only be used to convert between \
-                                         primitive types. Consider using the `From` trait")
+                                  .help(&format!("`match` on the `{}` value and construct the \
+                                                  corresponding `{}` variant in each arm",
+                                                 self.expr_ty,
+                                                 fcx.ty_to_string(self.cast_ty)))
                                   .emit();
             }
+            CastError::NonScalar => {
+                let mut err = type_error_struct!(fcx.tcx.sess, self.span, self.expr_ty, E0605,
+                                                 "non-primitive cast: `{}` as `{}`",
+                                                 self.expr_ty,
+                                                 fcx.ty_to_string(self.cast_ty));
+                self.suggest_newtype_field_cast(fcx, &mut err);
+                err.note("an `as` expression can only be used to convert between primitive \
+                          types. Consider using the `From` trait")
+                   .emit();
+            }
             CastError::SizedUnsizedCast => {
                 use crate::structured_errors::{SizedUnsizedCastError, StructuredDiagnostic};
                 SizedUnsizedCastError::new(&fcx.tcx.sess,
@@ -386,6 +405,73 @@ impl<'a, 'gcx, 'tcx> CastCheck<'tcx> {
         err.emit();
     }
 
+    /// If one side of a non-primitive cast is a tuple struct wrapping a single number and the
+    /// other side is a number, suggest going through the wrapped value instead.
+    fn suggest_newtype_field_cast(&self,
+                                  fcx: &FnCtxt<'a, 'gcx, 'tcx>,
+                                  err: &mut DiagnosticBuilder<'_>) {
+        let source_map = fcx.tcx.sess.source_map();
+        let snippet = match source_map.span_to_snippet(self.expr.span) {
+            Ok(snippet) => snippet,
+            Err(_) => return,
+        };
+        if is_number(self.cast_ty) {
+            if let Some(field_ty) = self.numeric_newtype_field(fcx, self.expr_ty) {
+                let needs_paren = self.expr.precedence().order() < (PREC_POSTFIX as i8);
+                let expr = if needs_paren { format!("({})", snippet) } else { snippet };
+                let (msg, sugg) = if field_ty == self.cast_ty {
+                    ("use the value wrapped by the tuple struct instead", format!("{}.0", expr))
+                } else {
+                    ("cast the value wrapped by the tuple struct instead",
+                     format!("{}.0 as {}", expr, fcx.ty_to_string(self.cast_ty)))
+                };
+                err.span_suggestion(self.span, msg, sugg, Applicability::MaybeIncorrect);
+            }
+        } else if is_number(self.expr_ty) {
+            if let Some(field_ty) = self.numeric_newtype_field(fcx, self.cast_ty) {
+                // use the type as written, so that the constructor is nameable here
+                let ctor = match source_map.span_to_snippet(self.cast_span) {
+                    Ok(ty) => ty.replacen('<', "::<", 1),
+                    Err(_) => return,
+                };
+                let arg = if self.expr_ty == field_ty {
+                    snippet
+                } else {
+                    format!("{} as {}", snippet, field_ty)
+                };
+                err.span_suggestion(
+                    self.span,
+                    "use the tuple struct constructor instead",
+                    format!("{}({})", ctor, arg),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+        }
+    }
+
+    /// Returns the type of the only field of `ty` if it is a tuple struct with a single,
+    /// accessible field of integer or float type.
+    fn numeric_newtype_field(&self, fcx: &FnCtxt<'a, 'gcx, 'tcx>, ty: Ty<'tcx>)
+                             -> Option<Ty<'tcx>> {
+        match ty.sty {
+            ty::Adt(def, substs) if def.is_struct() => {
+                let variant = def.non_enum_variant();
+                if variant.ctor_kind != CtorKind::Fn || variant.fields.len() != 1 {
+                    return None;
+                }
+                let field = &variant.fields[0];
+                let scope = fcx.tcx.hir().get_module_parent_by_hir_id(self.expr.hir_id);
+                let field_ty = field.ty(fcx.tcx, substs);
+                if field.vis.is_accessible_from(scope, fcx.tcx) && is_number(field_ty) {
+                    Some(field_ty)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
     fn trivial_cast_lint(&self, fcx: &FnCtxt<'a, 'gcx, 'tcx>) {
         let t_cast = self.cast_ty;
         let t_expr = self.expr_ty;
@@ -481,13 +567,37 @@ impl<'a, 'gcx, 'tcx> CastCheck<'tcx> {
             (Int(U(ast::UintTy::U8)), Int(Char)) => Ok(CastKind::U8CharCast), // u8-char-cast
             (_, Int(Char)) => Err(CastError::CastToChar),
 
-            // prim -> float,ptr
-            (Int(Bool), Float) |
-            (Int(CEnum), Float) |
-            (Int(Char), Float) => Err(CastError::NeedViaInt),
+            (Int(CEnum), _) => self.is_enum_cast(t_cast),
+            (Ptr(_), _) | (FnPtr, _) | (RPtr(_), _) | (_, Ptr(_)) => {
+                self.is_ptr_cast(fcx, t_from, t_cast)
+            }
+            _ => self.is_numeric_cast(t_from, t_cast),
+        }
+    }
+
+    /// Checks a cast from a C-like enum. Only casts to integers are allowed.
+    fn is_enum_cast(&self, t_cast: CastTy<'tcx>) -> Result<CastKind, CastError> {
+        use rustc::ty::cast::CastTy::*;
+
+        match t_cast {
+            Int(_) => Ok(CastKind::EnumCast),
+            Float => Err(CastError::NeedViaInt),
+            Ptr(_) => Err(CastError::IllegalCast),
+            FnPtr | RPtr(_) => Err(CastError::NonScalar),
+        }
+    }
+
+    /// Checks a cast from or to a pointer, be it raw, a reference or a function pointer.
+    fn is_ptr_cast(&self,
+                   fcx: &FnCtxt<'a, 'gcx, 'tcx>,
+                   t_from: CastTy<'tcx>,
+                   t_cast: CastTy<'tcx>)
+                   -> Result<CastKind, CastError> {
+        use rustc::ty::cast::IntTy::*;
+        use rustc::ty::cast::CastTy::*;
 
+        match (t_from, t_cast) {
             (Int(Bool), Ptr(_)) |
-            (Int(CEnum), Ptr(_)) |
             (Int(Char), Ptr(_)) |
             (Ptr(_), Float) |
             (FnPtr, Float) |
@@ -520,14 +630,28 @@ impl<'a, 'gcx, 'tcx> CastCheck<'tcx> {
             (FnPtr, Ptr(mt)) => self.check_fptr_ptr_cast(fcx, mt),
             (RPtr(rmt), Ptr(mt)) => self.check_ref_cast(fcx, rmt, mt), // array-ptr-cast
 
-            // prim -> prim
-            (Int(CEnum), Int(_)) => Ok(CastKind::EnumCast),
+            _ => bug!("not a pointer cast: {:?} as {:?}", t_from, t_cast),
+        }
+    }
+
+    /// Checks a cast between integers, floats, `bool`s and `char`s.
+    fn is_numeric_cast(&self, t_from: CastTy<'tcx>, t_cast: CastTy<'tcx>)
+                       -> Result<CastKind, CastError> {
+        use rustc::ty::cast::IntTy::*;
+        use rustc::ty::cast::CastTy::*;
+
+        match (t_from, t_cast) {
+            (Int(Bool), Float) |
+            (Int(Char), Float) => Err(CastError::NeedViaInt),
+
             (Int(Char), Int(_)) |
             (Int(Bool), Int(_)) => Ok(CastKind::PrimIntCast),
 
             (Int(_), Int(_)) | (Int(_), Float) | (Float, Int(_)) | (Float, Float) => {
                 Ok(CastKind::NumericCast)
             }
+
+            _ => bug!("not a numeric cast: {:?} as {:?}", t_from, t_cast),
         }
     }
 
@@ -76,6 +76,26 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             return;
         }
 
+        // The `use` items that brought each trait into scope at the call site, so that
+        // ambiguity errors can point at where every candidate was imported from.
+        let trait_imports = match (&error, source) {
+            (MethodError::Ambiguity(_), SelfSource::MethodCall(rcvr)) => {
+                let call_expr_id = self.tcx.hir().get_parent_node_by_hir_id(rcvr.hir_id);
+                self.tcx.in_scope_traits(call_expr_id)
+            }
+            _ => None,
+        };
+        let note_imports = |err: &mut DiagnosticBuilder<'_>, idx: usize, trait_did: DefId| {
+            let import_ids = trait_imports.iter()
+                .flat_map(|traits| traits.iter())
+                .filter(|candidate| candidate.def_id == trait_did)
+                .flat_map(|candidate| candidate.import_ids.iter());
+            for &import_id in import_ids {
+                err.span_note(self.tcx.hir().span(import_id),
+                              &format!("candidate #{} imported here", idx + 1));
+            }
+        };
+
         let report_candidates = |
             span: Span,
             err: &mut DiagnosticBuilder<'_>,
@@ -137,6 +157,9 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                         } else {
                             err.note(&note_str);
                         }
+                        if let Some(trait_ref) = self.tcx.impl_trait_ref(impl_did) {
+                            note_imports(err, idx, trait_ref.def_id);
+                        }
                     }
                     CandidateSource::TraitSource(trait_did) => {
                         let item = match self.associated_item(
@@ -161,6 +184,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                                        "the candidate is defined in the trait `{}`",
                                        self.tcx.def_path_str(trait_did));
                         }
+                        note_imports(err, idx, trait_did);
                         err.help(&format!("to disambiguate the method call, write `{}::{}({}{})` \
                                           instead",
                                           self.tcx.def_path_str(trait_did),
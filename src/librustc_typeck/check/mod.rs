@@ -2782,7 +2782,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         );
         self.check_argument_types(sp, expr_sp, &method.sig.inputs()[1..], &expected_arg_tys[..],
                                   args_no_rcvr, method.sig.c_variadic, tuple_arguments,
-                                  self.tcx.hir().span_if_local(method.def_id));
+                                  Some(method.def_id));
         method.sig.output()
     }
 
@@ -2862,8 +2862,16 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                             args: &'gcx [hir::Expr],
                             c_variadic: bool,
                             tuple_arguments: TupleArgumentsFlag,
-                            def_span: Option<Span>) {
+                            def_id: Option<DefId>) {
         let tcx = self.tcx;
+        let def_span = def_id.and_then(|def_id| tcx.hir().span_if_local(def_id));
+        // calling a tuple variant's constructor is reported in terms of the variant
+        let callee_kind = match def_id.map(|def_id| (def_id, tcx.def_kind(def_id))) {
+            Some((def_id, Some(DefKind::Ctor(CtorOf::Variant, _)))) => {
+                format!("enum variant `{}`", tcx.item_name(def_id))
+            }
+            _ => "function".to_string(),
+        };
 
         // Grab the argument types, supplying fresh type variables
         // if the wrong number of arguments were supplied
@@ -2881,13 +2889,15 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
 
         let expected_arg_count = fn_inputs.len();
 
-        let param_count_error = |expected_count: usize,
+        let param_count_error = |expected_tys: &[Ty<'tcx>],
                                  arg_count: usize,
                                  error_code: &str,
                                  c_variadic: bool,
                                  sugg_unit: bool| {
+            let expected_count = expected_tys.len();
             let mut err = tcx.sess.struct_span_err_with_code(sp,
-                &format!("this function takes {}{} but {} {} supplied",
+                &format!("this {} takes {}{} but {} {} supplied",
+                    callee_kind,
                     if c_variadic { "at least " } else { "" },
                     potentially_plural_count(expected_count, "parameter"),
                     potentially_plural_count(arg_count, "parameter"),
@@ -2910,6 +2920,26 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                 err.span_label(sp, format!("expected {}{}",
                                            if c_variadic { "at least " } else { "" },
                                            potentially_plural_count(expected_count, "parameter")));
+                // A method call's span only covers the method name, so also point at the
+                // closing parenthesis, where the missing arguments would go.
+                let missing_sp = tcx.sess.source_map().end_point(expr_sp);
+                if arg_count < expected_count && !sp.contains(missing_sp) {
+                    err.span_label(missing_sp, format!("missing {}",
+                        potentially_plural_count(expected_count - arg_count, "argument")));
+                }
+                let expected_tys = expected_tys.iter()
+                    .map(|ty| self.resolve_type_vars_if_possible(ty))
+                    .collect::<Vec<_>>();
+                // Only list the parameter types when all of them are known.
+                if !expected_tys.is_empty() &&
+                   !expected_tys.iter().any(|ty| ty.has_infer_types() || ty.references_error()) {
+                    err.note(&format!("expected parameter type{}: {}",
+                                      if expected_count == 1 { "" } else { "s" },
+                                      expected_tys.iter()
+                                          .map(|ty| format!("`{}`", self.ty_to_string(ty)))
+                                          .collect::<Vec<_>>()
+                                          .join(", ")));
+                }
             }
             err.emit();
         };
@@ -2920,7 +2950,8 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             let tuple_type = self.structurally_resolved_type(sp, fn_inputs[0]);
             match tuple_type.sty {
                 ty::Tuple(arg_types) if arg_types.len() != args.len() => {
-                    param_count_error(arg_types.len(), args.len(), "E0057", false, false);
+                    let arg_types = arg_types.iter().map(|k| k.expect_ty()).collect::<Vec<_>>();
+                    param_count_error(&arg_types, args.len(), "E0057", false, false);
                     expected_arg_tys = vec![];
                     self.err_args(args.len())
                 }
@@ -2948,7 +2979,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             if supplied_arg_count >= expected_arg_count {
                 fn_inputs.to_vec()
             } else {
-                param_count_error(expected_arg_count, supplied_arg_count, "E0060", true, false);
+                param_count_error(fn_inputs, supplied_arg_count, "E0060", true, false);
                 expected_arg_tys = vec![];
                 self.err_args(supplied_arg_count)
             }
@@ -2961,7 +2992,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
             } else {
                 false
             };
-            param_count_error(expected_arg_count, supplied_arg_count, "E0061", false, sugg_unit);
+            param_count_error(fn_inputs, supplied_arg_count, "E0061", false, sugg_unit);
 
             expected_arg_tys = vec![];
             self.err_args(supplied_arg_count)
@@ -3429,9 +3460,17 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
 
                 match expr_t.sty {
                     ty::Adt(def, _) if !def.is_enum() => {
-                        if let Some(suggested_field_name) =
-                            Self::suggest_field_name(def.non_enum_variant(),
-                                                     &field.as_str(), vec![]) {
+                        let variant = def.non_enum_variant();
+                        let is_tuple_index = field.as_str().parse::<usize>().is_ok();
+                        let suggested_field_name = if is_tuple_index &&
+                                                      variant.ctor_kind != CtorKind::Fn {
+                            err.note(&format!("numeric field access requires a tuple; `{}` is \
+                                               not a tuple", expr_t));
+                            Self::suggest_numbered_field_name(variant, &field.as_str())
+                        } else {
+                            Self::suggest_field_name(variant, &field.as_str(), vec![])
+                        };
+                        if let Some(suggested_field_name) = suggested_field_name {
                                 err.span_suggestion(
                                     field.span,
                                     "a field with a similar name exists",
@@ -3513,6 +3552,18 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         find_best_match_for_name(names, field, None)
     }
 
+    // Return a field whose name ends in the given tuple index, like `_0` or `field0`
+    fn suggest_numbered_field_name(variant: &'tcx ty::VariantDef, index: &str) -> Option<Symbol> {
+        variant.fields.iter().filter(|field| {
+            // ignore private fields from non-local crates
+            variant.def_id.is_local() || field.vis == Visibility::Public
+        }).map(|field| field.ident.name).find(|name| {
+            let name = name.as_str();
+            name.len() > index.len() && name.ends_with(index) &&
+                !name[..name.len() - index.len()].ends_with(|c: char| c.is_ascii_digit())
+        })
+    }
+
     fn available_field_names(&self, variant: &'tcx ty::VariantDef) -> Vec<ast::Name> {
         variant.fields.iter().filter(|field| {
             let def_scope = self.tcx.adjust_ident(field.ident, variant.def_id, self.body_id).1;
@@ -4965,14 +5016,25 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         }
     }
 
-    /// Given a `HirId`, return the `FnDecl` of the method it is enclosed by and whether a
-    /// suggestion can be made, `None` otherwise.
+    /// Given a `HirId`, return the `FnDecl` of the method or closure it is enclosed by and
+    /// whether a suggestion can be made, `None` otherwise.
     pub fn get_fn_decl(&self, blk_id: hir::HirId) -> Option<(hir::FnDecl, bool)> {
         // Get enclosing Fn, if it is a function or a trait method, unless there's a `loop` or
         // `while` before reaching it, as block tail returns are not available in them.
         self.tcx.hir().get_return_block(blk_id).and_then(|blk_id| {
-            let parent = self.tcx.hir().get_by_hir_id(blk_id);
-            self.get_node_fn_decl(parent).map(|(fn_decl, _, is_main)| (fn_decl, is_main))
+            match self.tcx.hir().get_by_hir_id(blk_id) {
+                // A closure's return type is inferred unless written out, in which case it
+                // can be pointed at like a function's.
+                Node::Expr(&hir::Expr {
+                    node: hir::ExprKind::Closure(_, ref decl, ..), ..
+                }) => match decl.output {
+                    hir::FunctionRetTy::Return(_) => Some(((**decl).clone(), false)),
+                    hir::FunctionRetTy::DefaultReturn(_) => None,
+                },
+                parent => {
+                    self.get_node_fn_decl(parent).map(|(fn_decl, _, is_main)| (fn_decl, is_main))
+                }
+            }
         })
     }
 
@@ -5631,11 +5693,14 @@ pub fn check_bounds_are_used<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         ty::GenericParamDefKind::Type { .. } => true,
         _ => false,
     });
+    // With `#![feature(unused_type_alias_params)]`, unused parameters are only warned about.
+    let is_warning = tcx.features().unused_type_alias_params;
     for (&used, param) in types_used.iter().zip(types) {
         if !used {
             let id = tcx.hir().as_local_hir_id(param.def_id).unwrap();
             let span = tcx.hir().span_by_hir_id(id);
-            struct_span_err!(tcx.sess, span, E0091, "type parameter `{}` is unused", param.name)
+            struct_span_err_or_warn!(is_warning, tcx.sess, span, E0091,
+                                     "type parameter `{}` is unused", param.name)
                 .span_label(span, "unused type parameter")
                 .emit();
         }
@@ -379,26 +379,27 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                                     }
                                 }
                             }
+                            // the trait and method that `lookup_op_method` searched for
                             let missing_trait = match op.node {
-                                hir::BinOpKind::Add    => Some("std::ops::Add"),
-                                hir::BinOpKind::Sub    => Some("std::ops::Sub"),
-                                hir::BinOpKind::Mul    => Some("std::ops::Mul"),
-                                hir::BinOpKind::Div    => Some("std::ops::Div"),
-                                hir::BinOpKind::Rem    => Some("std::ops::Rem"),
-                                hir::BinOpKind::BitAnd => Some("std::ops::BitAnd"),
-                                hir::BinOpKind::BitXor => Some("std::ops::BitXor"),
-                                hir::BinOpKind::BitOr  => Some("std::ops::BitOr"),
-                                hir::BinOpKind::Shl    => Some("std::ops::Shl"),
-                                hir::BinOpKind::Shr    => Some("std::ops::Shr"),
-                                hir::BinOpKind::Eq |
-                                hir::BinOpKind::Ne => Some("std::cmp::PartialEq"),
-                                hir::BinOpKind::Lt |
-                                hir::BinOpKind::Le |
-                                hir::BinOpKind::Gt |
-                                hir::BinOpKind::Ge => Some("std::cmp::PartialOrd"),
+                                hir::BinOpKind::Add    => Some(("std::ops::Add", "add")),
+                                hir::BinOpKind::Sub    => Some(("std::ops::Sub", "sub")),
+                                hir::BinOpKind::Mul    => Some(("std::ops::Mul", "mul")),
+                                hir::BinOpKind::Div    => Some(("std::ops::Div", "div")),
+                                hir::BinOpKind::Rem    => Some(("std::ops::Rem", "rem")),
+                                hir::BinOpKind::BitAnd => Some(("std::ops::BitAnd", "bitand")),
+                                hir::BinOpKind::BitXor => Some(("std::ops::BitXor", "bitxor")),
+                                hir::BinOpKind::BitOr  => Some(("std::ops::BitOr", "bitor")),
+                                hir::BinOpKind::Shl    => Some(("std::ops::Shl", "shl")),
+                                hir::BinOpKind::Shr    => Some(("std::ops::Shr", "shr")),
+                                hir::BinOpKind::Eq     => Some(("std::cmp::PartialEq", "eq")),
+                                hir::BinOpKind::Ne     => Some(("std::cmp::PartialEq", "ne")),
+                                hir::BinOpKind::Lt     => Some(("std::cmp::PartialOrd", "lt")),
+                                hir::BinOpKind::Le     => Some(("std::cmp::PartialOrd", "le")),
+                                hir::BinOpKind::Gt     => Some(("std::cmp::PartialOrd", "gt")),
+                                hir::BinOpKind::Ge     => Some(("std::cmp::PartialOrd", "ge")),
                                 _ => None
                             };
-                            if let Some(missing_trait) = missing_trait {
+                            if let Some((missing_trait, method)) = missing_trait {
                                 if op.node == hir::BinOpKind::Add &&
                                     self.check_str_addition(expr, lhs_expr, rhs_expr, lhs_ty,
                                                             rhs_ty, &mut err, false, op) {
@@ -414,8 +415,8 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                                 } else if !suggested_deref && !involves_fn {
                                     err.note(&format!(
                                         "an implementation of `{}` might \
-                                         be missing for `{}`",
-                                        missing_trait, lhs_ty
+                                         be missing for `{}`, to provide the `{}` method",
+                                        missing_trait, lhs_ty, method
                                     ));
                                 }
                             }
@@ -317,7 +317,7 @@ impl<'a> Classifier<'a> {
                         token::Str_(..) | token::StrRaw(..) => Class::String,
 
                     // Number literals.
-                    token::Integer(..) | token::Float(..) => Class::Number,
+                    token::Integer(..) | token::Float(..) | token::Frac(..) => Class::Number,
 
                     token::Bool(..) => panic!("literal token contains `Lit::Bool`"),
                 }
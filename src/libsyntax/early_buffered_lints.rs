@@ -8,10 +8,29 @@ use syntax_pos::MultiSpan;
 
 /// Since we cannot import `LintId`s from `rustc::lint`, we define some Ids here which can later be
 /// passed to `rustc::lint::Lint::from_parser_lint_id` to get a `rustc::lint::Lint`.
+#[derive(PartialEq)]
 pub enum BufferedEarlyLintId {
     /// Usage of `?` as a macro separator is deprecated.
     QuestionMarkMacroSep,
     IllFormedAttributeInput,
+    /// Whitespace at the end of a line.
+    TrailingWhitespace,
+    /// An integer literal followed by a method call or field access, like `1.foo()`.
+    IntegerLiteralMethodCall,
+    /// A source file that doesn't end with a newline.
+    MissingFinalNewline,
+    /// A decimal literal with a leading zero, like `0755`.
+    CStyleOctalLiterals,
+    /// A raw string literal that doesn't need to be raw, like `r"plain"`.
+    UnnecessaryRawStrings,
+    /// A tab character inside a doc comment.
+    TabsInDocComments,
+    /// A line of a raw string indented with both tabs and spaces.
+    MixedIndentationInRawStrings,
+    /// A source file with both `\n` and `\r\n` line endings.
+    MixedLineEndings,
+    /// A line with an odd number of quotes opening or closing a string literal.
+    OddQuotesOnLine,
 }
 
 /// Stores buffered lint info which can later be passed to `librustc`.
@@ -431,8 +431,8 @@ pub fn parse_failure_msg(tok: Token) -> String {
 fn token_name_eq(t1: &Token, t2: &Token) -> bool {
     if let (Some((id1, is_raw1)), Some((id2, is_raw2))) = (t1.ident(), t2.ident()) {
         id1.name == id2.name && is_raw1 == is_raw2
-    } else if let (Some(id1), Some(id2)) = (t1.lifetime(), t2.lifetime()) {
-        id1.name == id2.name
+    } else if let (Some((id1, is_raw1)), Some((id2, is_raw2))) = (t1.lifetime(), t2.lifetime()) {
+        id1.name == id2.name && is_raw1 == is_raw2
     } else {
         *t1 == *t2
     }
@@ -870,9 +870,9 @@ fn may_begin_with(name: &str, token: &Token) -> bool {
             _ => false,
         },
         "lifetime" => match *token {
-            Token::Lifetime(_) => true,
+            Token::Lifetime(..) => true,
             Token::Interpolated(ref nt) => match **nt {
-                token::NtLifetime(_) | token::NtTT(_) => true,
+                token::NtLifetime(..) | token::NtTT(_) => true,
                 _ => false,
             },
             _ => false,
@@ -935,8 +935,12 @@ fn parse_nt<'a>(p: &mut Parser<'a>, sp: Span, name: &str) -> Nonterminal {
         "path" => token::NtPath(panictry!(p.parse_path(PathStyle::Type))),
         "meta" => token::NtMeta(panictry!(p.parse_meta_item())),
         "vis" => token::NtVis(panictry!(p.parse_visibility(true))),
-        "lifetime" => if p.check_lifetime() {
-            token::NtLifetime(p.expect_lifetime().ident)
+        // this is also a single token; keyword names are reported where the
+        // lifetime is eventually parsed, not here
+        "lifetime" => if let Some((ident, is_raw)) = p.token.lifetime() {
+            let span = p.span;
+            p.bump();
+            token::NtLifetime(Ident::new(ident.name, span), is_raw)
         } else {
             let token_str = pprust::token_to_string(&p.token);
             p.fatal(&format!("expected a lifetime, found `{}`", &token_str)).emit();
@@ -554,6 +554,12 @@ declare_features! (
     // Allows using C-variadics.
     (active, c_variadic, "1.34.0", Some(44930), None),
 
+    // Allows raw lifetimes and labels with keyword names (e.g. `'r#fn`).
+    (active, raw_lifetimes, "1.35.0", None, None),
+
+    // Allows unused type parameters on type aliases, reporting them as warnings.
+    (active, unused_type_alias_params, "1.35.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -2453,6 +2459,15 @@ pub fn check_crate(krate: &ast::Crate,
         builtin_attributes: &*BUILTIN_ATTRIBUTE_MAP,
     };
     visit::walk_crate(visitor, krate);
+
+    // Raw identifiers are stable, but raw lifetimes and labels like `'r#fn` are not.
+    for &span in sess.raw_identifier_spans.borrow().iter() {
+        let is_lifetime = sess.source_map().span_to_snippet(span)
+            .map_or(false, |snippet| snippet.starts_with('\''));
+        if is_lifetime {
+            gate_feature!(&ctx, raw_lifetimes, span, "raw lifetimes and labels are experimental");
+        }
+    }
 }
 
 #[derive(Clone, Copy, Hash)]
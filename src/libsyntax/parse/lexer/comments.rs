@@ -136,6 +136,38 @@ pub fn strip_doc_comment_decoration(comment: &str) -> String {
     panic!("not a doc-comment: {}", comment);
 }
 
+/// Splits the contents of a doc-comment, as returned by
+/// `strip_doc_comment_decoration`, into its summary (the first non-blank
+/// line) and its body (everything after it, minus leading blank lines).
+pub fn doc_comment_summary(contents: &str) -> (&str, &str) {
+    /// Splits off the first line, returning it and the text after its newline.
+    fn split_line(s: &str) -> (&str, &str) {
+        match s.find('\n') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (s, ""),
+        }
+    }
+
+    let mut rest = contents;
+    while !rest.is_empty() {
+        let (line, tail) = split_line(rest);
+        rest = tail;
+        if !line.trim().is_empty() {
+            let mut body = rest;
+            while !body.is_empty() {
+                let (line, tail) = split_line(body);
+                if !line.trim().is_empty() {
+                    break;
+                }
+                body = tail;
+            }
+            return (line.trim(), body);
+        }
+    }
+
+    ("", "")
+}
+
 fn push_blank_line_comment(rdr: &StringReader<'_>, comments: &mut Vec<Comment>) {
     debug!(">>> blank-line comment");
     comments.push(Comment {
@@ -346,7 +378,7 @@ pub fn gather_comments(sess: &ParseSess, path: FileName, srdr: &mut dyn Read) ->
     srdr.read_to_string(&mut src).unwrap();
     let cm = SourceMap::new(sess.source_map().path_mapping().clone());
     let source_file = cm.new_source_file(path, src);
-    let mut rdr = lexer::StringReader::new_raw(sess, source_file, None);
+    let mut rdr = lexer::StringReader::new_raw(sess, source_file, None, Default::default());
 
     let mut comments: Vec<Comment> = Vec::new();
     let mut code_to_the_left = false; // Only code
@@ -430,4 +462,17 @@ mod tests {
         let stripped = strip_doc_comment_decoration("//test");
         assert_eq!(stripped, "test");
     }
+
+    #[test]
+    fn test_doc_comment_summary() {
+        let comment = "/**\n * Summary line.\n *\n * More details\n * here.\n */";
+        let stripped = strip_doc_comment_decoration(comment);
+        assert_eq!(doc_comment_summary(&stripped), ("Summary line.", " More details\n here."));
+
+        let stripped = strip_doc_comment_decoration("/// Only a summary");
+        assert_eq!(doc_comment_summary(&stripped), ("Only a summary", ""));
+
+        assert_eq!(doc_comment_summary("\n  \n First\nSecond"), ("First", "Second"));
+        assert_eq!(doc_comment_summary(" \n"), ("", ""));
+    }
 }
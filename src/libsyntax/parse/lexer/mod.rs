@@ -1,6 +1,7 @@
 use crate::ast::{self, Ident};
+use crate::early_buffered_lints::BufferedEarlyLintId;
 use crate::parse::{token, ParseSess};
-use crate::symbol::Symbol;
+use crate::symbol::{Interner, Symbol};
 use crate::parse::unescape;
 use crate::parse::unescape_error_reporting::{emit_unescape_error, push_escaped_char};
 
@@ -10,9 +11,12 @@ use core::unicode::property::Pattern_White_Space;
 
 use std::borrow::Cow;
 use std::char;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::iter;
 use std::mem::replace;
-use rustc_data_structures::sync::Lrc;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::{Lrc, Lock};
 use log::debug;
 
 pub mod comments;
@@ -34,6 +38,98 @@ impl Default for TokenAndSpan {
     }
 }
 
+/// The tokens of a source file, including whitespace and comments, in source order.
+pub struct LexedTokens {
+    pub tokens: Vec<TokenAndSpan>,
+}
+
+impl LexedTokens {
+    pub fn new(tokens: Vec<TokenAndSpan>) -> Self {
+        LexedTokens { tokens }
+    }
+
+    /// Returns `true` if `pos` lies within a comment, a doc-comment, or a string,
+    /// byte string, character or byte literal.
+    pub fn is_in_trivia_or_literal(&self, pos: BytePos) -> bool {
+        // The tokens are in source order and don't overlap.
+        let idx = self.tokens.binary_search_by(|t| {
+            if t.sp.hi() <= pos {
+                Ordering::Less
+            } else if t.sp.lo() > pos {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        });
+        let t = match idx {
+            Ok(idx) => &self.tokens[idx],
+            Err(_) => return false,
+        };
+        match t.tok {
+            token::Comment | token::DocComment(_) => true,
+            token::Literal(token::Byte(_), _) |
+            token::Literal(token::Char(_), _) |
+            token::Literal(token::Str_(_), _) |
+            token::Literal(token::StrRaw(..), _) |
+            token::Literal(token::ByteStr(_), _) |
+            token::Literal(token::ByteStrRaw(..), _) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the index of the nearest token before `idx` that `real_token`
+    /// would not skip, if any. Returns `None` if `idx` is past the end.
+    pub fn prev_significant(&self, idx: usize) -> Option<usize> {
+        self.tokens.get(..idx)?.iter().rposition(|t| !is_trivia(&t.tok))
+    }
+}
+
+/// Returns `true` if the delimiters in `tokens` nest correctly, with each
+/// closing delimiter matching the kind of the innermost open one.
+pub fn is_balanced(tokens: &[TokenAndSpan]) -> bool {
+    let mut open = Vec::new();
+    for t in tokens {
+        match t.tok {
+            token::OpenDelim(delim) => open.push(delim),
+            token::CloseDelim(delim) => {
+                if open.pop() != Some(delim) {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    open.is_empty()
+}
+
+/// Whether `tok` is whitespace, a (non-doc) comment or a shebang, which
+/// `real_token` skips over.
+fn is_trivia(tok: &token::Token) -> bool {
+    match *tok {
+        token::Whitespace | token::Comment | token::Shebang(_) => true,
+        _ => false,
+    }
+}
+
+/// An iterator over the tokens that `real_token` returns, each paired with its
+/// 1-based line number. Created by `StringReader::tokens_with_lines`.
+pub struct TokensWithLines<'r, 'a: 'r> {
+    reader: &'r mut StringReader<'a>,
+}
+
+impl<'r, 'a> Iterator for TokensWithLines<'r, 'a> {
+    type Item = (usize, TokenAndSpan);
+
+    fn next(&mut self) -> Option<(usize, TokenAndSpan)> {
+        let t = self.reader.real_token();
+        if t.tok == token::Eof {
+            return None;
+        }
+        let line = self.reader.source_file.lookup_line(t.sp.lo()).unwrap_or(0);
+        Some((line + 1, t))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct UnmatchedBrace {
     pub expected_delim: token::DelimToken,
@@ -43,6 +139,66 @@ pub struct UnmatchedBrace {
     pub candidate_span: Option<Span>,
 }
 
+/// How a `StringReader` lexes. The options are given when the reader is
+/// created, so they apply from the first token on.
+#[derive(Clone)]
+pub struct LexerOptions {
+    /// The interner for the symbols of the tokens produced. If `None`, the
+    /// global interner is used. Symbols from any other interner are only
+    /// meaningful to it and must be resolved through it, or through the
+    /// reader's own helpers like `idents_equal_lowercased`; in particular their
+    /// `Display` and `Debug` output and `as_str` still go through the global
+    /// interner.
+    pub interner: Option<Lrc<Lock<Interner>>>,
+    /// Whether `>>` is lexed as two `Gt` tokens rather than a single `Shr`, so
+    /// that nested generic argument lists close cleanly.
+    pub split_shr: bool,
+    /// Whether numeric literals with a `_` separator at the start or end of a
+    /// run of digits, like `0x_FF` or `1_.0`, or with several separators in a
+    /// row, like `1__000`, are rejected.
+    pub strict_digit_separators: bool,
+    /// Whether an unterminated raw string is lexed as a literal running to the
+    /// end of the file rather than aborting, its error being returned by
+    /// `take_recovered_errors`.
+    pub recover_raw_strings: bool,
+    /// Whether the exact text of whitespace tokens is recorded, to be retrieved
+    /// with `whitespace_text`.
+    pub keep_whitespace_text: bool,
+    /// The most block comments that may be open at once, counting the
+    /// outermost one, to bound the work done on untrusted input. A limit of 1
+    /// rejects any nested comment, and a limit of 0 rejects block comments
+    /// altogether.
+    pub max_comment_nesting: usize,
+    /// Whether hexadecimal floats with a binary exponent, like `0x1.8p-2`, are
+    /// lexed as single float literals. They are not part of the language, so
+    /// turning one into an AST literal is still an error.
+    pub hex_float_literals: bool,
+    /// Whether experimental rational literals like `3/4r` are lexed as single
+    /// `token::Frac` literals. Without the `r` suffix, `3/4` is still a
+    /// division.
+    pub rational_literals: bool,
+    /// Whether the lexer's style lints, like `trailing_whitespace` and
+    /// `missing_final_newline`, are checked for and buffered. They are all
+    /// allowed by default, so this is off unless a caller wants them.
+    pub style_lints: bool,
+}
+
+impl Default for LexerOptions {
+    fn default() -> Self {
+        LexerOptions {
+            interner: None,
+            split_shr: false,
+            strict_digit_separators: false,
+            recover_raw_strings: false,
+            keep_whitespace_text: false,
+            max_comment_nesting: usize::max_value(),
+            hex_float_literals: false,
+            rational_literals: false,
+            style_lints: false,
+        }
+    }
+}
+
 pub struct StringReader<'a> {
     crate sess: &'a ParseSess,
     /// The absolute offset within the source_map of the next character to read
@@ -58,6 +214,8 @@ pub struct StringReader<'a> {
     peek_tok: token::Token,
     peek_span: Span,
     peek_span_src_raw: Span,
+    /// Tokens lexed ahead by `peek_n`, following `peek_tok`.
+    peek_buf: VecDeque<PeekedToken>,
     fatal_errs: Vec<DiagnosticBuilder<'a>>,
     // cache a direct reference to the source text, so that we don't have to
     // retrieve it via `self.source_file.src.as_ref().unwrap()` all the time.
@@ -68,6 +226,8 @@ pub struct StringReader<'a> {
     span_src_raw: Span,
     /// Stack of open delimiters and their spans. Used for error message.
     open_braces: Vec<(token::DelimToken, Span)>,
+    /// The deepest `open_braces` has been while building token trees.
+    max_open_braces: usize,
     crate unmatched_braces: Vec<UnmatchedBrace>,
     /// The type and spans for all braces
     ///
@@ -75,6 +235,59 @@ pub struct StringReader<'a> {
     matching_delim_spans: Vec<(token::DelimToken, Span, Span)>,
     crate override_span: Option<Span>,
     last_unclosed_found_span: Option<Span>,
+    options: LexerOptions,
+    /// The errors recovered from so far, not emitted until the caller takes
+    /// them with `take_recovered_errors`.
+    recovered_errs: Vec<Diagnostic>,
+    /// The text of each whitespace token lexed so far, keyed by the start of
+    /// its span. Only recorded if `Some`.
+    whitespace_texts: Option<FxHashMap<BytePos, Symbol>>,
+    /// Whether `\n` and `\r\n` line endings have been seen so far, or `None`
+    /// once mixed line endings have been reported, or if style lints are off.
+    line_endings_seen: Option<(bool, bool)>,
+    /// The string literal quotes seen so far on the line of the last one.
+    line_quotes: Option<LineQuotes>,
+    /// The furthest position lexed before the last `reset_to`. Lints starting
+    /// before it have already been buffered, so they are not buffered again.
+    lints_buffered_up_to: BytePos,
+}
+
+/// A run of digits scanned by `StringReader::scan_digits`.
+struct ScannedDigits {
+    /// The position of the first character of the run.
+    start: BytePos,
+    /// The number of digits, not counting `_` separators.
+    len: usize,
+    /// Whether the first character of the run is a `_` separator.
+    leading_separator: bool,
+    /// Whether the last character of the run is a `_` separator.
+    trailing_separator: bool,
+    /// The position of the first `_` separator directly following another one.
+    repeated_separator: Option<BytePos>,
+    /// Whether the run contains a non-ASCII character that looks like a digit.
+    confusable: bool,
+}
+
+/// A token lexed ahead by `StringReader::peek_n`, with its raw span and the
+/// reader's position just past it, which is restored once it is consumed.
+struct PeekedToken {
+    token: TokenAndSpan,
+    span_src_raw: Span,
+    pos: BytePos,
+    next_pos: BytePos,
+    ch: Option<char>,
+}
+
+/// The quotes opening or closing a string literal found so far on one line,
+/// tracked by `StringReader::note_string_quote`.
+#[derive(Clone, Copy)]
+struct LineQuotes {
+    /// The 0-based index of the line.
+    line: usize,
+    /// The number of quotes on the line.
+    count: usize,
+    /// The position of the last of them.
+    last: BytePos,
 }
 
 impl<'a> StringReader<'a> {
@@ -90,7 +303,7 @@ impl<'a> StringReader<'a> {
     }
 
     fn mk_ident(&self, string: &str) -> Ident {
-        let mut ident = Ident::from_str(string);
+        let mut ident = Ident::with_empty_ctxt(self.intern(string));
         if let Some(span) = self.override_span {
             ident.span = span;
         }
@@ -120,12 +333,95 @@ impl<'a> StringReader<'a> {
             tok: replace(&mut self.peek_tok, token::Whitespace),
             sp: self.peek_span,
         };
-        self.advance_token()?;
+        match self.peek_buf.pop_front() {
+            Some(next) => {
+                self.peek_tok = next.token.tok;
+                self.peek_span = next.token.sp;
+                self.peek_span_src_raw = next.span_src_raw;
+                self.pos = next.pos;
+                self.next_pos = next.next_pos;
+                self.ch = next.ch;
+            }
+            None => self.advance_token()?,
+        }
         self.span_src_raw = self.peek_span_src_raw;
 
         Ok(ret_val)
     }
 
+    /// Returns the next `n` tokens without consuming them, the first being the
+    /// one `peek` returns. Fewer are returned if the file ends sooner. The
+    /// reader's position is left where it was, so `current_line_col` is not
+    /// affected by the tokens lexed ahead.
+    ///
+    /// Returns `Err(())` if lexing ahead fails, with the fatal errors pending
+    /// as for `try_next_token`.
+    pub fn peek_n(&mut self, n: usize) -> Result<Vec<TokenAndSpan>, ()> {
+        let (pos, next_pos, ch) = (self.pos, self.next_pos, self.ch);
+        if let Some(last) = self.peek_buf.back() {
+            // carry on lexing from just past the last token lexed ahead
+            self.pos = last.pos;
+            self.next_pos = last.next_pos;
+            self.ch = last.ch;
+        }
+
+        let mut res = Ok(());
+        while self.peek_buf.len() + 1 < n {
+            let last = self.peek_buf.back().map_or(&self.peek_tok, |t| &t.token.tok);
+            if *last == token::Eof {
+                break;
+            }
+
+            // lex the next token into the `peek_*` fields, then put the head back
+            let head = (replace(&mut self.peek_tok, token::Whitespace),
+                        self.peek_span,
+                        self.peek_span_src_raw);
+            res = self.advance_token();
+            let token = TokenAndSpan {
+                tok: replace(&mut self.peek_tok, head.0),
+                sp: replace(&mut self.peek_span, head.1),
+            };
+            let span_src_raw = replace(&mut self.peek_span_src_raw, head.2);
+            if res.is_err() {
+                break;
+            }
+            self.peek_buf.push_back(PeekedToken {
+                token,
+                span_src_raw,
+                pos: self.pos,
+                next_pos: self.next_pos,
+                ch: self.ch,
+            });
+        }
+
+        self.pos = pos;
+        self.next_pos = next_pos;
+        self.ch = ch;
+        res?;
+
+        Ok(iter::once(self.peek())
+            .chain(self.peek_buf.iter().map(|t| t.token.clone()))
+            .take(n)
+            .collect())
+    }
+
+    /// Lexes the rest of the file, returning every token up to but excluding
+    /// `Eof`, including whitespace and comments. Unlike `real_token`, fatal
+    /// errors are returned rather than emitted, so this never aborts.
+    pub fn tokenize_to_vec(&mut self) -> Result<Vec<TokenAndSpan>, Vec<Diagnostic>> {
+        if !self.fatal_errs.is_empty() {
+            return Err(self.buffer_fatal_errors());
+        }
+        let mut tokens = Vec::new();
+        loop {
+            match self.try_next_token() {
+                Ok(TokenAndSpan { tok: token::Eof, .. }) => return Ok(tokens),
+                Ok(t) => tokens.push(t),
+                Err(()) => return Err(self.buffer_fatal_errors()),
+            }
+        }
+    }
+
     /// Immutably extract string if found at current position with given delimiters
     fn peek_delimited(&self, from_ch: char, to_ch: char) -> Option<String> {
         let mut pos = self.pos;
@@ -150,13 +446,8 @@ impl<'a> StringReader<'a> {
 
     fn try_real_token(&mut self) -> Result<TokenAndSpan, ()> {
         let mut t = self.try_next_token()?;
-        loop {
-            match t.tok {
-                token::Whitespace | token::Comment | token::Shebang(_) => {
-                    t = self.try_next_token()?;
-                }
-                _ => break,
-            }
+        while is_trivia(&t.tok) {
+            t = self.try_next_token()?;
         }
 
         self.token = t.tok.clone();
@@ -170,12 +461,66 @@ impl<'a> StringReader<'a> {
         self.unwrap_or_abort(res)
     }
 
+    /// Returns the length in bytes of the token most recently returned by
+    /// `real_token`, or 0 if that token was `Eof`.
+    pub fn last_token_len(&self) -> usize {
+        if self.token == token::Eof {
+            0
+        } else {
+            (self.span.hi() - self.span.lo()).to_usize()
+        }
+    }
+
+    /// Returns the 1-based line and 0-based column of the reader's current
+    /// position. Columns count chars rather than bytes.
+    pub fn current_line_col(&self) -> (usize, usize) {
+        let line = self.source_file.lookup_line(self.pos).unwrap_or(0);
+        let line_start = self.source_file.lines.get(line)
+            .map_or(self.source_file.start_pos, |&start| start);
+        let col = self.src[self.src_index(line_start)..self.src_index(self.pos)].chars().count();
+        (line + 1, col)
+    }
+
+    /// Returns an iterator over the rest of the real tokens, each paired with
+    /// the 1-based line it starts on.
+    pub fn tokens_with_lines(&mut self) -> TokensWithLines<'_, 'a> {
+        TokensWithLines { reader: self }
+    }
+
+    /// Returns the maximum delimiter nesting depth reached so far while
+    /// building token trees.
+    pub fn max_nesting_depth(&self) -> usize {
+        self.max_open_braces
+    }
+
+    /// Returns the source text between the end of `a` and the start of `b`,
+    /// that is, the whitespace and comments separating the two tokens. Returns
+    /// `None` if `b` doesn't follow `a` in this reader's file, and always with
+    /// an override span, as the tokens' spans are then not their source spans.
+    pub fn text_between(&self, a: &TokenAndSpan, b: &TokenAndSpan) -> Option<&str> {
+        if self.override_span.is_some() {
+            return None;
+        }
+        let (lo, hi) = (a.sp.hi(), b.sp.lo());
+        if lo < self.source_file.start_pos || hi > self.source_file.end_pos || lo > hi {
+            return None;
+        }
+        Some(&self.src[self.src_index(lo)..self.src_index(hi)])
+    }
+
     #[inline]
     fn is_eof(&self) -> bool {
         self.ch.is_none()
     }
 
-    fn fail_unterminated_raw_string(&self, pos: BytePos, hash_count: u16) {
+    /// Reports an unterminated raw string. This is a fatal error unless the
+    /// reader recovers from raw strings, in which case the error is buffered
+    /// instead and lexing goes on. `found_hashes` is the longest run of `#`
+    /// seen after a `"` inside the string, if any.
+    fn fail_unterminated_raw_string(&mut self,
+                                    pos: BytePos,
+                                    hash_count: u16,
+                                    found_hashes: Option<u16>) -> Result<(), ()> {
         let mut err = self.struct_span_fatal(pos, pos, "unterminated raw string");
         err.span_label(self.mk_sp(pos, pos), "unterminated raw string");
 
@@ -183,9 +528,20 @@ impl<'a> StringReader<'a> {
             err.note(&format!("this raw string should be terminated with `\"{}`",
                               "#".repeat(hash_count as usize)));
         }
+        match found_hashes {
+            Some(found) if found > 0 => {
+                err.note(&format!("found a closing sequence with {} `#`, expected {}",
+                                  found, hash_count));
+            }
+            _ => {}
+        }
 
-        err.emit();
-        FatalError.raise();
+        if self.options.recover_raw_strings {
+            err.buffer(&mut self.recovered_errs);
+            return Ok(());
+        }
+        self.fatal_errs.push(err);
+        Err(())
     }
 
     fn fatal(&self, m: &str) -> FatalError {
@@ -210,6 +566,13 @@ impl<'a> StringReader<'a> {
         buffer
     }
 
+    /// Returns the errors this reader recovered from since the last call. They
+    /// are not reported otherwise, so a reader that recovers from errors must
+    /// have them taken and emitted by its caller.
+    pub fn take_recovered_errors(&mut self) -> Vec<Diagnostic> {
+        replace(&mut self.recovered_errs, Vec::new())
+    }
+
     pub fn peek(&self) -> TokenAndSpan {
         // FIXME(pcwalton): Bad copy!
         TokenAndSpan {
@@ -218,18 +581,124 @@ impl<'a> StringReader<'a> {
         }
     }
 
+    /// Returns the number of spaces and tabs that start the line containing
+    /// `pos`, counting a tab as one. Positions outside the file or inside a
+    /// character give 0.
+    pub fn line_indent(&self, pos: BytePos) -> usize {
+        if pos < self.source_file.start_pos || self.src_index(pos) > self.src.len() ||
+           !self.src.is_char_boundary(self.src_index(pos)) {
+            return 0;
+        }
+        let before = &self.src[..self.src_index(pos)];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        self.src[line_start..].chars().take_while(|&c| c == ' ' || c == '\t').count()
+    }
+
+    /// Whether the next token is the non-raw identifier `sym`.
+    pub fn peek_is_ident(&self, sym: Symbol) -> bool {
+        match self.peek_tok {
+            token::Ident(ident, false) => ident.name == sym,
+            _ => false,
+        }
+    }
+
+    /// If the next token is a shebang line at the very start of the file,
+    /// consumes it and returns its text and span. Otherwise, including for an
+    /// inner attribute like `#![feature(x)]`, the reader is left untouched.
+    pub fn take_shebang(&mut self) -> Option<(String, Span)> {
+        match self.peek_tok {
+            token::Shebang(_) if self.peek_span_src_raw.lo() == self.source_file.start_pos => {}
+            _ => return None,
+        }
+        let ts = self.peek();
+        let raw = self.peek_span_src_raw;
+        let text = self.src[self.src_index(raw.lo())..self.src_index(raw.hi())].to_string();
+        self.next_token();
+        Some((text, ts.sp))
+    }
+
+    /// Rewinds (or fast-forwards) this reader to `pos` and lexes the token
+    /// starting there, forgetting everything lexed so far, including the
+    /// delimiter bookkeeping done while building token trees and any pending
+    /// fatal errors. Errors recovered from are kept for `take_recovered_errors`.
+    ///
+    /// Returns `Err(())` without touching the reader if `pos` is not a
+    /// character boundary within the source file, before its end, and also if
+    /// lexing the token at `pos` fails, in which case the fatal errors are
+    /// pending as usual.
+    pub fn reset_to(&mut self, pos: BytePos) -> Result<(), ()> {
+        if pos < self.source_file.start_pos ||
+           self.src_index(pos) >= self.end_src_index ||
+           !self.src.is_char_boundary(self.src_index(pos)) {
+            return Err(());
+        }
+
+        for mut err in self.fatal_errs.drain(..) {
+            err.cancel();
+        }
+
+        self.lints_buffered_up_to = self.lints_buffered_up_to.max(self.pos);
+
+        // Pretend the character before `pos` was the last one read, so that
+        // `bump` sees the same `\r` before a `\n` that lexing up to `pos` would.
+        let prev = self.src[..self.src_index(pos)].chars().next_back();
+        self.ch = prev;
+        self.pos = pos - BytePos::from_usize(prev.map_or(0, char::len_utf8));
+        self.next_pos = pos;
+        self.bump();
+
+        self.peek_tok = token::Eof;
+        self.peek_span = syntax_pos::DUMMY_SP;
+        self.peek_span_src_raw = syntax_pos::DUMMY_SP;
+        self.peek_buf.clear();
+        self.token = token::Eof;
+        self.span = syntax_pos::DUMMY_SP;
+        self.span_src_raw = syntax_pos::DUMMY_SP;
+        self.open_braces.clear();
+        self.max_open_braces = 0;
+        self.unmatched_braces.clear();
+        self.matching_delim_spans.clear();
+        self.last_unclosed_found_span = None;
+        if let Some(ref mut texts) = self.whitespace_texts {
+            texts.clear();
+        }
+        // Once reported, mixed line endings are not looked for again.
+        if self.line_endings_seen.is_some() {
+            self.line_endings_seen = Some((false, false));
+        }
+        self.line_quotes = None;
+
+        self.advance_token()
+    }
+
+    /// Returns the source text of a token lexed by this reader, or an empty
+    /// string for `Eof`. Returns `None` if the token's span is not within this
+    /// reader's file, and always with an override span, as every token then
+    /// has that span and its text can't be told apart from the others'.
+    pub fn src_text(&self, ts: &TokenAndSpan) -> Option<&str> {
+        if ts.tok == token::Eof {
+            return Some("");
+        }
+        if self.override_span.is_some() ||
+           ts.sp.lo() < self.source_file.start_pos || ts.sp.hi() > self.source_file.end_pos {
+            return None;
+        }
+        Some(&self.src[self.src_index(ts.sp.lo())..self.src_index(ts.sp.hi())])
+    }
+
     /// For comments.rs, which hackily pokes into next_pos and ch
     fn new_raw(sess: &'a ParseSess,
                source_file: Lrc<syntax_pos::SourceFile>,
-               override_span: Option<Span>) -> Self {
-        let mut sr = StringReader::new_raw_internal(sess, source_file, override_span);
+               override_span: Option<Span>,
+               options: LexerOptions) -> Self {
+        let mut sr = StringReader::new_raw_internal(sess, source_file, override_span, options);
         sr.bump();
 
         sr
     }
 
     fn new_raw_internal(sess: &'a ParseSess, source_file: Lrc<syntax_pos::SourceFile>,
-        override_span: Option<Span>) -> Self
+        override_span: Option<Span>, options: LexerOptions) -> Self
     {
         if source_file.src.is_none() {
             sess.span_diagnostic.bug(&format!("Cannot lex source_file without source: {}",
@@ -249,23 +718,43 @@ impl<'a> StringReader<'a> {
             peek_tok: token::Eof,
             peek_span: syntax_pos::DUMMY_SP,
             peek_span_src_raw: syntax_pos::DUMMY_SP,
+            peek_buf: VecDeque::new(),
             src,
             fatal_errs: Vec::new(),
             token: token::Eof,
             span: syntax_pos::DUMMY_SP,
             span_src_raw: syntax_pos::DUMMY_SP,
             open_braces: Vec::new(),
+            max_open_braces: 0,
             unmatched_braces: Vec::new(),
             matching_delim_spans: Vec::new(),
             override_span,
             last_unclosed_found_span: None,
+            recovered_errs: Vec::new(),
+            whitespace_texts: if options.keep_whitespace_text {
+                Some(FxHashMap::default())
+            } else {
+                None
+            },
+            line_endings_seen: if options.style_lints { Some((false, false)) } else { None },
+            options,
+            line_quotes: None,
+            lints_buffered_up_to: BytePos(0),
         }
     }
 
     pub fn new_or_buffered_errs(sess: &'a ParseSess,
                                 source_file: Lrc<syntax_pos::SourceFile>,
                                 override_span: Option<Span>) -> Result<Self, Vec<Diagnostic>> {
-        let mut sr = StringReader::new_raw(sess, source_file, override_span);
+        StringReader::new_with_options(sess, source_file, override_span, LexerOptions::default())
+    }
+
+    /// Like `new_or_buffered_errs`, but lexing with `options`.
+    pub fn new_with_options(sess: &'a ParseSess,
+                            source_file: Lrc<syntax_pos::SourceFile>,
+                            override_span: Option<Span>,
+                            options: LexerOptions) -> Result<Self, Vec<Diagnostic>> {
+        let mut sr = StringReader::new_raw(sess, source_file, override_span, options);
         if sr.advance_token().is_err() {
             Err(sr.buffer_fatal_errors())
         } else {
@@ -273,7 +762,10 @@ impl<'a> StringReader<'a> {
         }
     }
 
-    pub fn retokenize(sess: &'a ParseSess, mut span: Span) -> Self {
+    /// Creates a reader over the source text of `span`, which has not lexed
+    /// any token yet. Lexing stops at the end of the span, as `end_src_index`
+    /// is set to it.
+    fn new_raw_for_span(sess: &'a ParseSess, mut span: Span) -> Self {
         let begin = sess.source_map().lookup_byte_offset(span.lo());
         let end = sess.source_map().lookup_byte_offset(span.hi());
 
@@ -282,13 +774,18 @@ impl<'a> StringReader<'a> {
             span = span.shrink_to_lo();
         }
 
-        let mut sr = StringReader::new_raw_internal(sess, begin.sf, None);
+        let mut sr = StringReader::new_raw_internal(sess, begin.sf, None, LexerOptions::default());
 
         // Seek the lexer to the right byte range.
         sr.next_pos = span.lo();
         sr.end_src_index = sr.src_index(span.hi());
 
         sr.bump();
+        sr
+    }
+
+    pub fn retokenize(sess: &'a ParseSess, span: Span) -> Self {
+        let mut sr = StringReader::new_raw_for_span(sess, span);
 
         if sr.advance_token().is_err() {
             sr.emit_fatal_errors();
@@ -298,6 +795,56 @@ impl<'a> StringReader<'a> {
         sr
     }
 
+    /// Like `retokenize`, but lexes all of `span` at once, keeping whitespace
+    /// and comments so that the tokens cover the span's text exactly. Nothing
+    /// past the end of the span is lexed. Fatal errors are returned rather than
+    /// emitted.
+    pub fn retokenize_verbose(sess: &'a ParseSess, span: Span)
+        -> Result<Vec<TokenAndSpan>, Vec<Diagnostic>>
+    {
+        let mut sr = StringReader::new_raw_for_span(sess, span);
+        if sr.advance_token().is_err() {
+            return Err(sr.buffer_fatal_errors());
+        }
+        sr.tokenize_to_vec()
+    }
+
+    /// Returns the text of a whitespace token lexed with the
+    /// `keep_whitespace_text` option, or `None` for any other token.
+    pub fn whitespace_text(&self, ts: &TokenAndSpan) -> Option<Symbol> {
+        match ts.tok {
+            token::Whitespace => self.whitespace_texts.as_ref()?.get(&ts.sp.lo()).cloned(),
+            _ => None,
+        }
+    }
+
+    /// Whether two identifiers from this reader's interner are equal once
+    /// lowercased. ASCII names are compared directly; otherwise both names are
+    /// compared one lowercased `char` at a time.
+    ///
+    /// This is not Unicode case folding: names that only match once folded, like
+    /// `STRASSE` and `straße`, are not equal.
+    pub fn idents_equal_lowercased(&self, a: Symbol, b: Symbol) -> bool {
+        if a == b {
+            return true;
+        }
+        match self.options.interner {
+            Some(ref interner) => {
+                let interner = interner.lock();
+                strs_equal_lowercased(interner.get(a), interner.get(b))
+            }
+            None => strs_equal_lowercased(&a.as_str(), &b.as_str()),
+        }
+    }
+
+    /// Maps a string to a symbol in this reader's interner.
+    fn intern(&self, string: &str) -> Symbol {
+        match self.options.interner {
+            Some(ref interner) => interner.lock().intern(string),
+            None => Symbol::intern(string),
+        }
+    }
+
     #[inline]
     fn ch_is(&self, c: char) -> bool {
         self.ch == Some(c)
@@ -308,32 +855,26 @@ impl<'a> StringReader<'a> {
         self.sess.span_diagnostic.span_fatal(sp, m)
     }
 
+    /// Buffer an early lint with a given span, unless this reader was reset
+    /// back over it after buffering it the first time.
+    fn buffer_lint(&self, lint_id: BufferedEarlyLintId, sp: Span, m: &str) {
+        if sp.lo() < self.lints_buffered_up_to {
+            return;
+        }
+        self.sess.buffer_lint(lint_id, sp, ast::CRATE_NODE_ID, m);
+    }
+
     /// Report a lexical error with a given span.
     fn err_span(&self, sp: Span, m: &str) {
         self.sess.span_diagnostic.struct_span_err(sp, m).emit();
     }
 
 
-    /// Report a fatal error spanning [`from_pos`, `to_pos`).
-    fn fatal_span_(&self, from_pos: BytePos, to_pos: BytePos, m: &str) -> FatalError {
-        self.fatal_span(self.mk_sp(from_pos, to_pos), m)
-    }
-
     /// Report a lexical error spanning [`from_pos`, `to_pos`).
     fn err_span_(&self, from_pos: BytePos, to_pos: BytePos, m: &str) {
         self.err_span(self.mk_sp(from_pos, to_pos), m)
     }
 
-    /// Report a lexical error spanning [`from_pos`, `to_pos`), appending an
-    /// escaped character to the error message
-    fn fatal_span_char(&self, from_pos: BytePos, to_pos: BytePos, m: &str, c: char) -> FatalError {
-        let mut m = m.to_string();
-        m.push_str(": ");
-        push_escaped_char(&mut m, c);
-
-        self.fatal_span_(from_pos, to_pos, &m[..])
-    }
-
     fn struct_span_fatal(&self, from_pos: BytePos, to_pos: BytePos, m: &str)
         -> DiagnosticBuilder<'a>
     {
@@ -350,6 +891,16 @@ impl<'a> StringReader<'a> {
         self.sess.span_diagnostic.struct_span_fatal(self.mk_sp(from_pos, to_pos), &m[..])
     }
 
+    fn struct_err_span_char(&self, from_pos: BytePos, to_pos: BytePos, m: &str, c: char)
+        -> DiagnosticBuilder<'a>
+    {
+        let mut m = m.to_string();
+        m.push_str(": ");
+        push_escaped_char(&mut m, c);
+
+        self.sess.span_diagnostic.struct_span_err(self.mk_sp(from_pos, to_pos), &m[..])
+    }
+
     /// Report a lexical error spanning [`from_pos`, `to_pos`), appending an
     /// escaped character to the error message
     fn err_span_char(&self, from_pos: BytePos, to_pos: BytePos, m: &str, c: char) {
@@ -362,7 +913,7 @@ impl<'a> StringReader<'a> {
     /// Advance peek_tok and peek_span to refer to the next token, and
     /// possibly update the interner.
     fn advance_token(&mut self) -> Result<(), ()> {
-        match self.scan_whitespace_or_comment() {
+        match self.scan_whitespace_or_comment()? {
             Some(comment) => {
                 self.peek_span_src_raw = comment.sp;
                 self.peek_span = comment.sp;
@@ -375,6 +926,11 @@ impl<'a> StringReader<'a> {
                         self.source_file.end_pos,
                         self.source_file.end_pos,
                     );
+                    // only check once, not every time `Eof` is requested again
+                    if self.peek_span_src_raw != raw {
+                        self.check_final_newline();
+                        self.check_line_quotes();
+                    }
                     self.peek_span = real;
                     self.peek_span_src_raw = raw;
                 } else {
@@ -407,13 +963,13 @@ impl<'a> StringReader<'a> {
     /// Creates a Name from a given offset to the current offset.
     fn name_from(&self, start: BytePos) -> ast::Name {
         debug!("taking an ident from {:?} to {:?}", start, self.pos);
-        self.with_str_from(start, Symbol::intern)
+        self.with_str_from(start, |string| self.intern(string))
     }
 
     /// As name_from, with an explicit endpoint.
     fn name_from_to(&self, start: BytePos, end: BytePos) -> ast::Name {
         debug!("taking an ident from {:?} to {:?}", start, end);
-        self.with_str_from_to(start, end, Symbol::intern)
+        self.with_str_from_to(start, end, |string| self.intern(string))
     }
 
     /// Calls `f` with a string slice of the source text spanning from `start`
@@ -477,6 +1033,11 @@ impl<'a> StringReader<'a> {
             let next_ch = char_at(&self.src, next_src_index);
             let next_ch_len = next_ch.len_utf8();
 
+            if next_ch == '\n' && self.line_endings_seen.is_some() {
+                let crlf = self.pos < self.next_pos && self.ch_is('\r');
+                self.note_line_ending(self.next_pos, crlf);
+            }
+
             self.ch = Some(next_ch);
             self.pos = self.next_pos;
             self.next_pos = self.next_pos + Pos::from_usize(next_ch_len);
@@ -486,6 +1047,59 @@ impl<'a> StringReader<'a> {
         }
     }
 
+    /// Records a line ending at `newline`, linting the file the first time it
+    /// is found to mix `\n` and `\r\n`.
+    fn note_line_ending(&mut self, newline: BytePos, crlf: bool) {
+        let (lf, crlf_seen) = self.line_endings_seen.unwrap();
+        let seen = if crlf { (lf, true) } else { (true, crlf_seen) };
+        if seen != (true, true) {
+            self.line_endings_seen = Some(seen);
+            return;
+        }
+        let lo = if crlf { newline - BytePos(1) } else { newline };
+        self.buffer_lint(
+            BufferedEarlyLintId::MixedLineEndings,
+            self.mk_sp(lo, newline + BytePos(1)),
+            "file uses mixed line endings",
+        );
+        self.line_endings_seen = None;
+    }
+
+    /// Records the `"` at `pos` that opens or closes a string literal. The
+    /// first time one is found on a later line, the previous line is checked.
+    fn note_string_quote(&mut self, pos: BytePos) {
+        if !self.options.style_lints {
+            return;
+        }
+        let line = self.source_file.lookup_line(pos).unwrap_or(0);
+        if let Some(ref mut quotes) = self.line_quotes {
+            if quotes.line == line {
+                quotes.count += 1;
+                quotes.last = pos;
+                return;
+            }
+        }
+        self.check_line_quotes();
+        self.line_quotes = Some(LineQuotes { line, count: 1, last: pos });
+    }
+
+    /// Lints the line tracked in `line_quotes` if it has an odd number of
+    /// string literal quotes, which often means a quote is missing. This is
+    /// only a heuristic: a string spanning several lines triggers it too.
+    fn check_line_quotes(&mut self) {
+        let quotes = match self.line_quotes.take() {
+            Some(quotes) => quotes,
+            None => return,
+        };
+        if quotes.count % 2 == 1 {
+            self.buffer_lint(
+                BufferedEarlyLintId::OddQuotesOnLine,
+                self.mk_sp(quotes.last, quotes.last + BytePos(1)),
+                "line has an odd number of `\"`; a string literal may be unterminated",
+            );
+        }
+    }
+
     fn nextch(&self) -> Option<char> {
         let next_src_index = self.src_index(self.next_pos);
         if next_src_index < self.end_src_index {
@@ -543,14 +1157,26 @@ impl<'a> StringReader<'a> {
                     .emit();
                 None
             } else {
-                Some(Symbol::intern(string))
+                match self.sess.max_literal_suffix_len {
+                    Some(max) if string.chars().count() > max => {
+                        self.sess.span_diagnostic
+                            .struct_span_warn(self.mk_sp(start, self.pos),
+                                              &format!("literal suffix `{}` is suspiciously long",
+                                                       string))
+                            .note(&format!("suffixes longer than {} characters are usually a \
+                                            mistake, like a missing operator", max))
+                            .emit();
+                    }
+                    _ => {}
+                }
+                Some(self.intern(string))
             }
         })
     }
 
     /// PRECONDITION: self.ch is not whitespace
     /// Eats any kind of comment.
-    fn scan_comment(&mut self) -> Option<TokenAndSpan> {
+    fn scan_comment(&mut self) -> Result<Option<TokenAndSpan>, ()> {
         if let Some(c) = self.ch {
             if c.is_whitespace() {
                 let msg = "called consume_any_line_comment, but there was whitespace";
@@ -585,29 +1211,31 @@ impl<'a> StringReader<'a> {
                         }
                         self.bump();
                     }
+                    self.check_line_comment_trailing_whitespace(start_bpos);
 
                     let tok = if doc_comment {
+                        self.check_doc_comment_tabs(start_bpos);
                         self.with_str_from(start_bpos, |string| {
-                            token::DocComment(Symbol::intern(string))
+                            token::DocComment(self.intern(string))
                         })
                     } else {
                         token::Comment
                     };
-                    Some(TokenAndSpan { tok, sp: self.mk_sp(start_bpos, self.pos) })
+                    Ok(Some(TokenAndSpan { tok, sp: self.mk_sp(start_bpos, self.pos) }))
                 }
                 Some('*') => {
                     self.bump();
                     self.bump();
-                    self.scan_block_comment()
+                    self.scan_block_comment().map(Some)
                 }
-                _ => None,
+                _ => Ok(None),
             }
         } else if self.ch_is('#') {
             if self.nextch_is('!') {
 
                 // Parse an inner attribute.
                 if self.nextnextch_is('[') {
-                    return None;
+                    return Ok(None);
                 }
 
                 let is_beginning_of_file = self.pos == self.source_file.start_pos;
@@ -617,71 +1245,168 @@ impl<'a> StringReader<'a> {
                     while !self.ch_is('\n') && !self.is_eof() {
                         self.bump();
                     }
-                    return Some(TokenAndSpan {
+                    return Ok(Some(TokenAndSpan {
                         tok: token::Shebang(self.name_from(start)),
                         sp: self.mk_sp(start, self.pos),
-                    });
+                    }));
                 }
             }
-            None
+            Ok(None)
         } else {
-            None
+            Ok(None)
         }
     }
 
     /// If there is whitespace, shebang, or a comment, scan it. Otherwise,
     /// return `None`.
-    fn scan_whitespace_or_comment(&mut self) -> Option<TokenAndSpan> {
+    fn scan_whitespace_or_comment(&mut self) -> Result<Option<TokenAndSpan>, ()> {
         match self.ch.unwrap_or('\0') {
             // # to handle shebang at start of file -- this is the entry point
             // for skipping over all "junk"
             '/' | '#' => {
-                let c = self.scan_comment();
+                let c = self.scan_comment()?;
                 debug!("scanning a comment {:?}", c);
-                c
+                Ok(c)
             },
             c if is_pattern_whitespace(Some(c)) => {
                 let start_bpos = self.pos;
                 while is_pattern_whitespace(self.ch) {
                     self.bump();
                 }
+                self.check_trailing_whitespace(start_bpos);
+                let sp = self.mk_sp(start_bpos, self.pos);
+                if self.whitespace_texts.is_some() {
+                    let text = self.name_from(start_bpos);
+                    self.whitespace_texts.as_mut().unwrap().insert(sp.lo(), text);
+                }
                 let c = Some(TokenAndSpan {
                     tok: token::Whitespace,
-                    sp: self.mk_sp(start_bpos, self.pos),
+                    sp,
                 });
                 debug!("scanning whitespace: {:?}", c);
-                c
+                Ok(c)
             }
-            _ => None,
+            _ => Ok(None),
+        }
+    }
+
+    /// Buffers a `trailing_whitespace` lint for each line ended within the
+    /// whitespace run from `start` up to the current position, or by the end of
+    /// the file, that has whitespace before its end. That includes lines with
+    /// nothing but whitespace on them.
+    fn check_trailing_whitespace(&self, start: BytePos) {
+        if !self.options.style_lints {
+            return;
+        }
+        let run = &self.src[self.src_index(start)..self.src_index(self.pos)];
+        let mut line_start = start;
+        let mut lines = run.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            // the run's last line only ends here if the file does
+            let ended = lines.peek().is_some() || self.is_eof();
+            let trailing_len = line.trim_end_matches('\r').len();
+            if ended && trailing_len > 0 {
+                self.buffer_lint(
+                    BufferedEarlyLintId::TrailingWhitespace,
+                    self.mk_sp(line_start, line_start + Pos::from_usize(trailing_len)),
+                    "trailing whitespace",
+                );
+            }
+            line_start = line_start + Pos::from_usize(line.len() + 1);
         }
     }
 
+    /// Buffers a `trailing_whitespace` lint if the line comment from `start`
+    /// up to the current position ends with whitespace.
+    fn check_line_comment_trailing_whitespace(&self, start: BytePos) {
+        if !self.options.style_lints {
+            return;
+        }
+        let text = &self.src[self.src_index(start)..self.src_index(self.pos)];
+        let trailing_len = text.len() - text.trim_end().len();
+        if trailing_len > 0 {
+            self.buffer_lint(
+                BufferedEarlyLintId::TrailingWhitespace,
+                self.mk_sp(self.pos - Pos::from_usize(trailing_len), self.pos),
+                "trailing whitespace",
+            );
+        }
+    }
+
+    /// Buffers a `tabs_in_doc_comments` lint for the first tab in the doc
+    /// comment from `start` up to the current position.
+    fn check_doc_comment_tabs(&self, start: BytePos) {
+        if !self.options.style_lints {
+            return;
+        }
+        let text = &self.src[self.src_index(start)..self.src_index(self.pos)];
+        if let Some(offset) = text.find('\t') {
+            let tab = start + Pos::from_usize(offset);
+            self.buffer_lint(
+                BufferedEarlyLintId::TabsInDocComments,
+                self.mk_sp(tab, tab + BytePos(1)),
+                "tab character in doc comment",
+            );
+        }
+    }
+
+    /// Lints a source file whose last character isn't a newline.
+    fn check_final_newline(&self) {
+        if !self.options.style_lints {
+            return;
+        }
+        if !self.source_file.name.is_real() || self.end_src_index != self.src.len() {
+            return;
+        }
+        let last = match self.src.chars().next_back() {
+            Some('\n') | None => return,
+            Some(c) => c,
+        };
+        let end = self.source_file.end_pos;
+        self.buffer_lint(
+            BufferedEarlyLintId::MissingFinalNewline,
+            self.mk_sp(end - Pos::from_usize(last.len_utf8()), end),
+            "missing newline at end of file",
+        );
+    }
+
     /// Might return a sugared-doc-attr
-    fn scan_block_comment(&mut self) -> Option<TokenAndSpan> {
+    fn scan_block_comment(&mut self) -> Result<TokenAndSpan, ()> {
         // block comments starting with "/**" or "/*!" are doc-comments
         let is_doc_comment = self.ch_is('*') || self.ch_is('!');
         let start_bpos = self.pos - BytePos(2);
 
-        let mut level: isize = 1;
+        // the positions of the `/*`s that are still open, outermost first
+        let mut openers = Vec::new();
+        self.open_block_comment(&mut openers, start_bpos)?;
         let mut has_cr = false;
-        while level > 0 {
+        while !openers.is_empty() {
             if self.is_eof() {
                 let msg = if is_doc_comment {
                     "unterminated block doc-comment"
                 } else {
                     "unterminated block comment"
                 };
-                let last_bpos = self.pos;
-                self.fatal_span_(start_bpos, last_bpos, msg).raise();
+                let innermost = openers.pop().unwrap();
+                let mut err = self.struct_span_fatal(innermost, innermost + BytePos(2), msg);
+                err.span_label(self.mk_sp(innermost, innermost + BytePos(2)),
+                               "this comment is never closed");
+                for &opener in openers.iter().rev() {
+                    err.span_note(self.mk_sp(opener, opener + BytePos(2)),
+                                  "it is nested in this comment, which is not closed either");
+                }
+                self.fatal_errs.push(err);
+                return Err(());
             }
             let n = self.ch.unwrap();
             match n {
                 '/' if self.nextch_is('*') => {
-                    level += 1;
+                    let pos = self.pos;
+                    self.open_block_comment(&mut openers, pos)?;
                     self.bump();
                 }
                 '*' if self.nextch_is('/') => {
-                    level -= 1;
+                    openers.pop();
                     self.bump();
                 }
                 '\r' => {
@@ -695,6 +1420,7 @@ impl<'a> StringReader<'a> {
         self.with_str_from(start_bpos, |string| {
             // but comments with only "*"s between two "/"s are not
             let tok = if is_block_doc_comment(string) {
+                self.check_doc_comment_tabs(start_bpos);
                 let string = if has_cr {
                     self.translate_crlf(start_bpos,
                                         string,
@@ -702,32 +1428,74 @@ impl<'a> StringReader<'a> {
                 } else {
                     string.into()
                 };
-                token::DocComment(Symbol::intern(&string[..]))
+                token::DocComment(self.intern(&string[..]))
             } else {
                 token::Comment
             };
 
-            Some(TokenAndSpan {
+            Ok(TokenAndSpan {
                 tok,
                 sp: self.mk_sp(start_bpos, self.pos),
             })
         })
     }
 
+    /// Records the block comment opened by the `/*` at `pos`, failing if that
+    /// would leave more comments open than `max_comment_nesting` allows.
+    fn open_block_comment(&mut self, openers: &mut Vec<BytePos>, pos: BytePos)
+                          -> Result<(), ()> {
+        let max = self.options.max_comment_nesting;
+        if openers.len() >= max {
+            let mut err = self.struct_span_fatal(pos, pos + BytePos(2),
+                                                 "block comment nested too deeply");
+            err.note(&format!("at most {} block comment{} may be open at once",
+                              max, if max == 1 { "" } else { "s" }));
+            self.fatal_errs.push(err);
+            return Err(());
+        }
+        openers.push(pos);
+        Ok(())
+    }
+
     /// Scan through any digits (base `scan_radix`) or underscores,
     /// and return how many digits there were.
     ///
     /// `real_radix` represents the true radix of the number we're
     /// interested in, and errors will be emitted for any digits
     /// between `real_radix` and `scan_radix`.
-    fn scan_digits(&mut self, real_radix: u32, scan_radix: u32) -> usize {
+    fn scan_digits(&mut self, real_radix: u32, scan_radix: u32) -> ScannedDigits {
         assert!(real_radix <= scan_radix);
-        let mut len = 0;
+        let mut digits = ScannedDigits {
+            start: self.pos,
+            len: 0,
+            leading_separator: self.ch_is('_'),
+            trailing_separator: false,
+            repeated_separator: None,
+            confusable: false,
+        };
 
         loop {
             let c = self.ch;
             if c == Some('_') {
                 debug!("skipping a _");
+                if digits.trailing_separator && digits.repeated_separator.is_none() {
+                    digits.repeated_separator = Some(self.pos);
+                }
+                digits.trailing_separator = true;
+                self.bump();
+                continue;
+            }
+            if let Some(ch) = c.filter(|&ch| unicode_chars::is_confusable_digit(ch)) {
+                // report it, but keep the literal in one piece
+                let mut err = self.struct_err_span_char(self.pos,
+                                                        self.next_pos,
+                                                        "unknown character in numeric literal",
+                                                        ch);
+                unicode_chars::check_for_substitution(self, ch, &mut err);
+                err.emit();
+                digits.len += 1;
+                digits.trailing_separator = false;
+                digits.confusable = true;
                 self.bump();
                 continue;
             }
@@ -741,10 +1509,11 @@ impl<'a> StringReader<'a> {
                                        self.next_pos,
                                        &format!("invalid digit for a base {} literal", real_radix));
                     }
-                    len += 1;
+                    digits.len += 1;
+                    digits.trailing_separator = false;
                     self.bump();
                 }
-                _ => return len,
+                _ => return digits,
             }
         }
     }
@@ -755,7 +1524,7 @@ impl<'a> StringReader<'a> {
         let start_bpos = self.pos;
         self.bump();
 
-        let num_digits = if c == '0' {
+        let digits = if c == '0' {
             match self.ch.unwrap_or('\0') {
                 'b' => {
                     self.bump();
@@ -773,7 +1542,7 @@ impl<'a> StringReader<'a> {
                     self.scan_digits(16, 16)
                 }
                 '0'..='9' | '_' | '.' | 'e' | 'E' => {
-                    self.scan_digits(10, 10) + 1
+                    self.scan_digits_after_first()
                 }
                 _ => {
                     // just a 0
@@ -781,102 +1550,369 @@ impl<'a> StringReader<'a> {
                 }
             }
         } else if c.is_digit(10) {
-            self.scan_digits(10, 10) + 1
+            self.scan_digits_after_first()
         } else {
-            0
+            ScannedDigits {
+                start: self.pos,
+                len: 0,
+                leading_separator: false,
+                trailing_separator: false,
+                repeated_separator: None,
+                confusable: false,
+            }
         };
 
-        if num_digits == 0 {
+        if digits.len == 0 {
             self.err_span_(start_bpos, self.pos, "no valid digits found for number");
 
-            return token::Integer(Symbol::intern("0"));
+            return token::Integer(self.intern("0"));
         }
+        self.check_digit_separators(&digits);
+        let mut confusable = digits.confusable;
 
         // might be a float, but don't be greedy if this is actually an
         // integer literal followed by field/method access or a range pattern
         // (`0..2` and `12.foo()`)
+        let hex_float = base == 16 && self.options.hex_float_literals;
         if self.ch_is('.') && !self.nextch_is('.') &&
-           !ident_start(self.nextch()) {
+           (!ident_start(self.nextch()) || (hex_float && self.at_hex_fraction())) {
             // might have stuff after the ., and if it does, it needs to start
-            // with a number
+            // with a number (or a hexadecimal digit or binary exponent, for
+            // hexadecimal floats)
             self.bump();
-            if self.ch.unwrap_or('\0').is_digit(10) {
-                self.scan_digits(10, 10);
-                self.scan_float_exponent();
+            let mut binary_exponent = false;
+            if hex_float && (self.ch.unwrap_or('\0').is_digit(16) || self.at_binary_exponent()) {
+                let digits = self.scan_digits(16, 16);
+                self.check_digit_separators(&digits);
+                confusable |= digits.confusable;
+                if self.ch_is('p') || self.ch_is('P') {
+                    binary_exponent = true;
+                    confusable |= self.scan_binary_exponent();
+                }
+            } else if self.ch.unwrap_or('\0').is_digit(10) {
+                let digits = self.scan_digits(10, 10);
+                self.check_digit_separators(&digits);
+                confusable |= digits.confusable;
+                confusable |= self.scan_float_exponent();
+            }
+            if confusable {
+                return token::Err(self.name_from(start_bpos));
             }
             let pos = self.pos;
-            self.check_float_base(start_bpos, pos, base);
+            if !binary_exponent && self.check_float_base(start_bpos, pos, base) {
+                return token::Err(self.name_from(start_bpos));
+            }
 
             token::Float(self.name_from(start_bpos))
         } else {
+            if confusable {
+                return token::Err(self.name_from(start_bpos));
+            }
+            // hexadecimal floats may also be written without a fractional part,
+            // as long as the `p` can't be the start of a suffix like in `0x10px`
+            if base == 16 && self.options.hex_float_literals && self.at_binary_exponent() {
+                if self.scan_binary_exponent() {
+                    return token::Err(self.name_from(start_bpos));
+                }
+                return token::Float(self.name_from(start_bpos));
+            }
             // it might be a float if it has an exponent
-            if self.ch_is('e') || self.ch_is('E') {
-                self.scan_float_exponent();
+            if self.at_float_exponent() {
+                if self.scan_float_exponent() {
+                    return token::Err(self.name_from(start_bpos));
+                }
                 let pos = self.pos;
-                self.check_float_base(start_bpos, pos, base);
+                if self.check_float_base(start_bpos, pos, base) {
+                    return token::Err(self.name_from(start_bpos));
+                }
                 return token::Float(self.name_from(start_bpos));
             }
             // but we certainly have an integer!
+            if base == 10 {
+                self.check_integer_method_call(start_bpos);
+                if c == '0' {
+                    self.check_c_style_octal(start_bpos);
+                }
+            }
             token::Integer(self.name_from(start_bpos))
         }
     }
 
-    /// Scan over a float exponent.
-    fn scan_float_exponent(&mut self) {
-        if self.ch_is('e') || self.ch_is('E') {
-            self.bump();
-
-            if self.ch_is('-') || self.ch_is('+') {
-                self.bump();
-            }
+    /// Lex a rational literal like `3/4r`: decimal digits, a `/`, more decimal
+    /// digits and an `r` that doesn't start a longer suffix. If the input
+    /// doesn't have this form, nothing is consumed and `None` is returned.
+    fn scan_frac(&mut self) -> Option<token::Lit> {
+        let rest = &self.src[self.src_index(self.pos)..self.end_src_index];
+        let digits = |s: &str| s.bytes().take_while(|b| b.is_ascii_digit()).count();
+        let numerator_len = digits(rest);
+        if numerator_len == 0 || !rest[numerator_len..].starts_with('/') {
+            return None;
+        }
+        let denominator_len = digits(&rest[numerator_len + 1..]);
+        let len = numerator_len + 1 + denominator_len;
+        if denominator_len == 0 || !rest[len..].starts_with('r') ||
+           ident_continue(rest[len + 1..].chars().next()) {
+            return None;
+        }
 
-            if self.scan_digits(10, 10) == 0 {
-                let mut err = self.struct_span_fatal(
-                    self.pos, self.next_pos,
-                    "expected at least one digit in exponent"
-                );
-                if let Some(ch) = self.ch {
-                    // check for e.g., Unicode minus '−' (Issue #49746)
-                    if unicode_chars::check_for_substitution(self, ch, &mut err) {
-                        self.bump();
-                        self.scan_digits(10, 10);
-                    }
-                }
-                err.emit();
-            }
+        let numerator_start = self.pos;
+        let numerator_end = numerator_start + Pos::from_usize(numerator_len);
+        let denominator_start = numerator_end + BytePos(1);
+        let denominator_end = denominator_start + Pos::from_usize(denominator_len);
+        // everything up to and including the `r` suffix is ASCII
+        for _ in 0..len + 1 {
+            self.bump();
         }
+        let numerator = self.name_from_to(numerator_start, numerator_end);
+        let denominator = self.name_from_to(denominator_start, denominator_end);
+        Some(token::Frac(numerator, denominator))
     }
 
-    /// Checks that a base is valid for a floating literal, emitting a nice
-    /// error if it isn't.
-    fn check_float_base(&mut self, start_bpos: BytePos, last_bpos: BytePos, base: usize) {
-        match base {
-            16 => {
-                self.err_span_(start_bpos,
-                               last_bpos,
-                               "hexadecimal float literal is not supported")
-            }
-            8 => {
-                self.err_span_(start_bpos,
-                               last_bpos,
-                               "octal float literal is not supported")
-            }
-            2 => {
-                self.err_span_(start_bpos,
-                               last_bpos,
-                               "binary float literal is not supported")
-            }
-            _ => (),
+    /// Whether the current character is an `e` or `E` that starts a float
+    /// exponent, rather than a suffix like the `ex` in `1ex`.
+    fn at_float_exponent(&self) -> bool {
+        if !self.ch_is('e') && !self.ch_is('E') {
+            return false;
+        }
+        match self.nextch() {
+            Some(c) if c.is_digit(10) || c == '_' || unicode_chars::is_confusable_digit(c) => true,
+            next => !ident_continue(next),
         }
     }
 
-    fn binop(&mut self, op: token::BinOpToken) -> token::Token {
-        self.bump();
-        if self.ch_is('=') {
+    /// Scan over a float exponent. Returns `true` if it contained a digit
+    /// that only looks like an ASCII one, which has already been reported.
+    fn scan_float_exponent(&mut self) -> bool {
+        if self.at_float_exponent() {
             self.bump();
-            token::BinOpEq(op)
+            self.scan_exponent_digits()
         } else {
-            token::BinOp(op)
+            false
+        }
+    }
+
+    /// Whether the current character is a `p` or `P` followed by a sign or a
+    /// digit, so that it starts the binary exponent of a hexadecimal float
+    /// rather than a suffix.
+    fn at_binary_exponent(&self) -> bool {
+        if !self.ch_is('p') && !self.ch_is('P') {
+            return false;
+        }
+        match self.nextch() {
+            Some(c) => {
+                c.is_digit(10) || c == '+' || c == '-' || unicode_chars::is_confusable_digit(c)
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the `.` at the current position starts the fraction of a
+    /// hexadecimal float like `0x1.Ap3` or `0x1.p4`, rather than a field
+    /// access or method call like `0xff.abs()`. A fraction starting with a
+    /// letter only counts if its digits are followed by a binary exponent.
+    fn at_hex_fraction(&self) -> bool {
+        let rest = &self.src[self.src_index(self.next_pos)..self.end_src_index];
+        if !rest.starts_with(|c: char| c.is_digit(16) || c == 'p' || c == 'P') {
+            return false;
+        }
+        let mut chars = rest.chars().skip_while(|&c| c.is_digit(16) || c == '_');
+        match chars.next() {
+            Some('p') | Some('P') => {}
+            _ => return false,
+        }
+        match chars.next() {
+            Some(c) => {
+                c.is_digit(10) || c == '+' || c == '-' || unicode_chars::is_confusable_digit(c)
+            }
+            None => false,
+        }
+    }
+
+    /// Scan over the binary exponent of a hexadecimal float, like the `p-2`
+    /// in `0x1.8p-2`, starting at its `p`. Returns `true` if it contained a
+    /// digit that only looks like an ASCII one, which has already been reported.
+    fn scan_binary_exponent(&mut self) -> bool {
+        self.bump();
+        self.scan_exponent_digits()
+    }
+
+    /// Scan over the optionally signed decimal digits of an exponent. Returns
+    /// whether any of them was a confusable digit, as `ScannedDigits::confusable`.
+    fn scan_exponent_digits(&mut self) -> bool {
+        if self.ch_is('-') || self.ch_is('+') {
+            self.bump();
+        }
+
+        let digits = self.scan_digits(10, 10);
+        if digits.len == 0 && self.pos > digits.start {
+            self.err_span_(digits.start, self.pos,
+                           "exponent requires at least one digit, found only separators");
+        } else if digits.len == 0 {
+            let mut err = self.struct_span_fatal(
+                self.pos, self.next_pos,
+                "expected at least one digit in exponent"
+            );
+            let mut confusable = false;
+            if let Some(ch) = self.ch {
+                // check for e.g., Unicode minus '−' (Issue #49746)
+                if unicode_chars::check_for_substitution(self, ch, &mut err) {
+                    self.bump();
+                    confusable = self.scan_digits(10, 10).confusable;
+                }
+            }
+            err.emit();
+            return confusable;
+        } else {
+            self.check_digit_separators(&digits);
+        }
+        digits.confusable
+    }
+
+    /// Scans the rest of a decimal number whose first digit has already been consumed.
+    fn scan_digits_after_first(&mut self) -> ScannedDigits {
+        let digits = self.scan_digits(10, 10);
+        ScannedDigits { len: digits.len + 1, leading_separator: false, ..digits }
+    }
+
+    /// With strict digit separators, reports a `_` at the start or end of the
+    /// run of digits that was just scanned.
+    fn check_digit_separators(&self, digits: &ScannedDigits) {
+        if !self.options.strict_digit_separators {
+            return;
+        }
+        if digits.leading_separator {
+            self.err_span_(digits.start, digits.start + BytePos(1),
+                           "digit separator cannot appear at the start of a number");
+        }
+        if let Some(pos) = digits.repeated_separator {
+            self.err_span_(pos, pos + BytePos(1),
+                           "digit separators cannot appear next to each other");
+        }
+        if digits.trailing_separator {
+            self.err_span_(self.pos - BytePos(1), self.pos,
+                           "digit separator cannot appear at the end of a number");
+        }
+    }
+
+    /// Notes an integer literal immediately followed by `.` and an identifier, like `1.foo()`,
+    /// which accesses `foo` on the integer `1` rather than on the float `1.0`.
+    fn check_integer_method_call(&self, start_bpos: BytePos) {
+        if !self.options.style_lints {
+            return;
+        }
+        let start_idx = self.src_index(start_bpos);
+        // `tuple.0.foo` is a field access chain, not a literal
+        if self.src[..start_idx].ends_with('.') {
+            return;
+        }
+        if !self.ch_is('.') || !ident_start(self.nextch()) {
+            return;
+        }
+        let lit = &self.src[start_idx..self.src_index(self.pos)];
+        self.buffer_lint(
+            BufferedEarlyLintId::IntegerLiteralMethodCall,
+            self.mk_sp(start_bpos, self.pos),
+            &format!("this accesses a member of the integer `{}`, not of the float `{}.0`",
+                     lit, lit),
+        );
+    }
+
+    /// Notes a raw string without `#`s whose contents have no backslashes, so
+    /// it could be written as a normal string literal.
+    fn check_unnecessary_raw_string(&self,
+                                    start_bpos: BytePos,
+                                    content_start_bpos: BytePos,
+                                    content_end_bpos: BytePos) {
+        if !self.options.style_lints {
+            return;
+        }
+        let content = &self.src[self.src_index(content_start_bpos)..
+                                self.src_index(content_end_bpos)];
+        if content.contains('\\') {
+            return;
+        }
+        self.buffer_lint(
+            BufferedEarlyLintId::UnnecessaryRawStrings,
+            self.mk_sp(start_bpos, self.pos),
+            "raw string literal has no backslashes, so it can be a normal string literal",
+        );
+    }
+
+    /// Notes the first line of a raw string, other than the one it starts on,
+    /// whose indentation mixes tabs and spaces.
+    fn check_raw_string_indentation(&self,
+                                    content_start_bpos: BytePos,
+                                    content_end_bpos: BytePos) {
+        if !self.options.style_lints {
+            return;
+        }
+        let content = &self.src[self.src_index(content_start_bpos)..
+                                self.src_index(content_end_bpos)];
+        for (newline, _) in content.match_indices('\n') {
+            let line_start = newline + 1;
+            let indent = content[line_start..].find(|c| c != ' ' && c != '\t')
+                .unwrap_or(content.len() - line_start);
+            let indent_text = &content[line_start..line_start + indent];
+            if indent_text.contains(' ') && indent_text.contains('\t') {
+                let lo = content_start_bpos + Pos::from_usize(line_start);
+                self.buffer_lint(
+                    BufferedEarlyLintId::MixedIndentationInRawStrings,
+                    self.mk_sp(lo, lo + Pos::from_usize(indent)),
+                    "raw string line is indented with both tabs and spaces",
+                );
+                return;
+            }
+        }
+    }
+
+    /// Notes a decimal integer literal with a leading zero, like `0755`, which
+    /// C would read as octal.
+    fn check_c_style_octal(&self, start_bpos: BytePos) {
+        if !self.options.style_lints {
+            return;
+        }
+        let lit = &self.src[self.src_index(start_bpos)..self.src_index(self.pos)];
+        let digits = lit.trim_start_matches(|c| c == '0' || c == '_');
+        if digits.is_empty() {
+            // all zeros, which is zero in any base
+            return;
+        }
+        let msg = if digits.chars().all(|c| c == '_' || c.is_digit(8)) {
+            format!("`{}` is a decimal literal, not an octal one; write `0o{}` for octal",
+                    lit, digits)
+        } else {
+            format!("`{}` is a decimal literal with a leading zero, \
+                     and has an invalid digit for octal", lit)
+        };
+        self.buffer_lint(
+            BufferedEarlyLintId::CStyleOctalLiterals,
+            self.mk_sp(start_bpos, self.pos),
+            &msg,
+        );
+    }
+
+    /// Checks that a base is valid for a floating literal, emitting a nice
+    /// error if it isn't. Returns `true` if an error was emitted, in which
+    /// case the caller produces a `token::Err` literal so the parser can
+    /// skip it cleanly.
+    fn check_float_base(&mut self, start_bpos: BytePos, last_bpos: BytePos, base: usize) -> bool {
+        let msg = match base {
+            16 => "hexadecimal float literal is not supported",
+            8 => "octal float literal is not supported",
+            2 => "binary float literal is not supported",
+            _ => return false,
+        };
+        self.err_span_(start_bpos, last_bpos, msg);
+        true
+    }
+
+    fn binop(&mut self, op: token::BinOpToken) -> token::Token {
+        self.bump();
+        if self.ch_is('=') {
+            self.bump();
+            token::BinOpEq(op)
+        } else {
+            token::BinOp(op)
         }
     }
 
@@ -925,7 +1961,8 @@ impl<'a> StringReader<'a> {
                     if is_raw_ident {
                         let span = self.mk_sp(raw_start, self.pos);
                         if !ident.can_be_raw() {
-                            self.err_span(span, &format!("`{}` cannot be a raw identifier", ident));
+                            self.err_span(span,
+                                          &format!("`{}` cannot be a raw identifier", string));
                         }
                         self.sess.raw_identifier_spans.borrow_mut().push(span);
                     }
@@ -936,8 +1973,18 @@ impl<'a> StringReader<'a> {
         }
 
         if is_dec_digit(c) {
+            if self.options.rational_literals {
+                if let Some(frac) = self.scan_frac() {
+                    debug!("next_token_inner: scanned rational {:?}", frac);
+                    return Ok(token::Literal(frac, None));
+                }
+            }
             let num = self.scan_number(c.unwrap());
-            let suffix = self.scan_optional_raw_name();
+            let mut suffix = self.scan_optional_raw_name();
+            if let token::Err(_) = num {
+                // The literal has already been reported, don't complain about its suffix too.
+                suffix = None;
+            }
             debug!("next_token_inner: scanned number {:?}, {:?}", num, suffix);
             return Ok(token::Literal(num, suffix));
         }
@@ -1072,6 +2119,9 @@ impl<'a> StringReader<'a> {
                         self.bump();
                         Ok(token::Ge)
                     }
+                    '>' if self.options.split_shr => {
+                        Ok(token::Gt)
+                    }
                     '>' => {
                         Ok(self.binop(token::Shr))
                     }
@@ -1086,6 +2136,28 @@ impl<'a> StringReader<'a> {
                 self.bump();
                 let start = self.pos;
 
+                // A raw lifetime, like `'r#fn`
+                if self.ch_is('r') && self.nextch_is('#') && ident_start(self.nextnextch()) {
+                    // Consume the 'r#' characters.
+                    self.bump();
+                    self.bump();
+                    let name_start = self.pos;
+                    while ident_continue(self.ch) {
+                        self.bump();
+                    }
+
+                    let span = self.mk_sp(start_with_quote, self.pos);
+                    let ident = self.with_str_from(name_start, |name| {
+                        if !self.mk_ident(name).can_be_raw() {
+                            self.err_span(span, &format!("`{}` cannot be a raw lifetime", name));
+                        }
+                        self.mk_ident(&format!("'{}", name))
+                    });
+                    self.sess.raw_identifier_spans.borrow_mut().push(span);
+
+                    return Ok(token::Lifetime(ident, true));
+                }
+
                 // If the character is an ident start not followed by another single
                 // quote, then this is a lifetime name:
                 let starts_with_number = self.ch.unwrap_or('\x00').is_numeric();
@@ -1119,10 +2191,27 @@ impl<'a> StringReader<'a> {
                         );
                     }
 
-                    return Ok(token::Lifetime(ident));
+                    return Ok(token::Lifetime(ident, false));
+                }
+
+                // A `'` followed by whitespace that doesn't lead up to a closing `'` is more
+                // likely a misplaced lifetime or label than an unterminated character literal.
+                if is_pattern_whitespace(self.ch) {
+                    let rest = &self.src[self.src_index(self.pos)..self.end_src_index];
+                    if rest.chars().find(|&c| !is_pattern_whitespace(Some(c))) != Some('\'') {
+                        let mut err = self.struct_span_fatal(start_with_quote, start,
+                                                             "lone `'` in source");
+                        err.span_label(self.mk_sp(start_with_quote, start),
+                                       "expected a character literal, lifetime or label");
+                        err.help("a lifetime or label name must directly follow the `'`, \
+                                  like `'a`");
+                        self.fatal_errs.push(err);
+                        return Err(());
+                    }
                 }
+
                 let msg = "unterminated character literal";
-                let id = self.scan_single_quoted_string(start_with_quote, msg);
+                let id = self.scan_single_quoted_string(start_with_quote, msg)?;
                 self.validate_char_escape(start_with_quote);
                 let suffix = self.scan_optional_raw_name();
                 Ok(token::Literal(token::Char(id), suffix))
@@ -1134,18 +2223,18 @@ impl<'a> StringReader<'a> {
                         let start_with_quote = self.pos;
                         self.bump();
                         let msg = "unterminated byte constant";
-                        let id = self.scan_single_quoted_string(start_with_quote, msg);
+                        let id = self.scan_single_quoted_string(start_with_quote, msg)?;
                         self.validate_byte_escape(start_with_quote);
                         token::Byte(id)
                     },
                     Some('"') => {
                         let start_with_quote = self.pos;
                         let msg = "unterminated double quote byte string";
-                        let id = self.scan_double_quoted_string(msg);
+                        let id = self.scan_double_quoted_string(msg)?;
                         self.validate_byte_str_escape(start_with_quote);
                         token::ByteStr(id)
                     },
-                    Some('r') => self.scan_raw_byte_string(),
+                    Some('r') => self.scan_raw_byte_string()?,
                     _ => unreachable!(),  // Should have been a token::Ident above.
                 };
                 let suffix = self.scan_optional_raw_name();
@@ -1155,7 +2244,7 @@ impl<'a> StringReader<'a> {
             '"' => {
                 let start_with_quote = self.pos;
                 let msg = "unterminated double quote string";
-                let id = self.scan_double_quoted_string(msg);
+                let id = self.scan_double_quoted_string(msg)?;
                 self.validate_str_escape(start_with_quote);
                 let suffix = self.scan_optional_raw_name();
                 Ok(token::Literal(token::Str_(id), suffix))
@@ -1167,33 +2256,43 @@ impl<'a> StringReader<'a> {
                 while self.ch_is('#') {
                     if hash_count == 65535 {
                         let bpos = self.next_pos;
-                        self.fatal_span_(start_bpos,
-                                         bpos,
-                                         "too many `#` symbols: raw strings may be \
-                                         delimited by up to 65535 `#` symbols").raise();
+                        let err = self.struct_span_fatal(start_bpos,
+                                                         bpos,
+                                                         "too many `#` symbols: raw strings may \
+                                                          be delimited by up to 65535 `#` \
+                                                          symbols");
+                        self.fatal_errs.push(err);
+                        return Err(());
                     }
                     self.bump();
                     hash_count += 1;
                 }
 
                 if self.is_eof() {
-                    self.fail_unterminated_raw_string(start_bpos, hash_count);
+                    self.fail_unterminated_raw_string(start_bpos, hash_count, None)?;
+                    let id = self.intern("");
+                    return Ok(token::Literal(token::StrRaw(id, hash_count), None));
                 } else if !self.ch_is('"') {
                     let last_bpos = self.pos;
                     let curr_char = self.ch.unwrap();
-                    self.fatal_span_char(start_bpos,
-                                         last_bpos,
-                                         "found invalid character; only `#` is allowed \
-                                         in raw string delimitation",
-                                         curr_char).raise();
+                    let err = self.struct_fatal_span_char(start_bpos,
+                                                          last_bpos,
+                                                          "found invalid character; only `#` is \
+                                                           allowed in raw string delimitation",
+                                                          curr_char);
+                    self.fatal_errs.push(err);
+                    return Err(());
                 }
                 self.bump();
                 let content_start_bpos = self.pos;
                 let mut content_end_bpos;
                 let mut valid = true;
+                let mut found_hashes = None;
                 'outer: loop {
                     if self.is_eof() {
-                        self.fail_unterminated_raw_string(start_bpos, hash_count);
+                        self.fail_unterminated_raw_string(start_bpos, hash_count, found_hashes)?;
+                        let id = self.name_from(content_start_bpos);
+                        return Ok(token::Literal(token::StrRaw(id, hash_count), None));
                     }
                     // if self.ch_is('"') {
                     // content_end_bpos = self.pos;
@@ -1205,9 +2304,10 @@ impl<'a> StringReader<'a> {
                     match c {
                         '"' => {
                             content_end_bpos = self.pos;
-                            for _ in 0..hash_count {
+                            for found in 0..hash_count {
                                 self.bump();
                                 if !self.ch_is('#') {
+                                    found_hashes = found_hashes.max(Some(found));
                                     continue 'outer;
                                 }
                             }
@@ -1232,8 +2332,15 @@ impl<'a> StringReader<'a> {
                 let id = if valid {
                     self.name_from_to(content_start_bpos, content_end_bpos)
                 } else {
-                    Symbol::intern("??")
+                    self.intern("??")
                 };
+                if valid && hash_count == 0 {
+                    self.check_unnecessary_raw_string(start_bpos, content_start_bpos,
+                                                      content_end_bpos);
+                }
+                if valid {
+                    self.check_raw_string_indentation(content_start_bpos, content_end_bpos);
+                }
                 let suffix = self.scan_optional_raw_name();
 
                 Ok(token::Literal(token::StrRaw(id, hash_count), suffix))
@@ -1333,7 +2440,7 @@ impl<'a> StringReader<'a> {
 
     fn scan_single_quoted_string(&mut self,
                                  start_with_quote: BytePos,
-                                 unterminated_msg: &str) -> ast::Name {
+                                 unterminated_msg: &str) -> Result<ast::Name, ()> {
         // assumes that first `'` is consumed
         let start = self.pos;
         // lex `'''` as a single char, for recovery
@@ -1356,8 +2463,10 @@ impl<'a> StringReader<'a> {
                         || (self.ch_is('/') && !first)
                         || (self.ch_is('\n') && !self.nextch_is('\'')) {
 
-                        self.fatal_span_(start_with_quote, self.pos, unterminated_msg.into())
-                            .raise()
+                        let pos = self.pos;
+                        let err = self.struct_span_fatal(start_with_quote, pos, unterminated_msg);
+                        self.fatal_errs.push(err);
+                        return Err(());
                     }
                     self.bump();
                 }
@@ -1367,18 +2476,21 @@ impl<'a> StringReader<'a> {
 
         let id = self.name_from(start);
         self.bump();
-        id
+        Ok(id)
     }
 
-    fn scan_double_quoted_string(&mut self, unterminated_msg: &str) -> ast::Name {
+    fn scan_double_quoted_string(&mut self, unterminated_msg: &str) -> Result<ast::Name, ()> {
         debug_assert!(self.ch_is('\"'));
         let start_with_quote = self.pos;
+        self.note_string_quote(start_with_quote);
         self.bump();
         let start = self.pos;
         while !self.ch_is('"') {
             if self.is_eof() {
                 let pos = self.pos;
-                self.fatal_span_(start_with_quote, pos, unterminated_msg).raise();
+                let err = self.struct_span_fatal(start_with_quote, pos, unterminated_msg);
+                self.fatal_errs.push(err);
+                return Err(());
             }
             if self.ch_is('\\') && (self.nextch_is('\\') || self.nextch_is('"')) {
                 self.bump();
@@ -1386,50 +2498,75 @@ impl<'a> StringReader<'a> {
             self.bump();
         }
         let id = self.name_from(start);
+        let end_quote = self.pos;
+        self.note_string_quote(end_quote);
         self.bump();
-        id
+        Ok(id)
     }
 
-    fn scan_raw_byte_string(&mut self) -> token::Lit {
+    fn scan_raw_byte_string(&mut self) -> Result<token::Lit, ()> {
         let start_bpos = self.pos;
         self.bump();
         let mut hash_count = 0;
         while self.ch_is('#') {
             if hash_count == 65535 {
                 let bpos = self.next_pos;
-                self.fatal_span_(start_bpos,
-                                 bpos,
-                                 "too many `#` symbols: raw byte strings may be \
-                                 delimited by up to 65535 `#` symbols").raise();
+                let err = self.struct_span_fatal(start_bpos,
+                                                 bpos,
+                                                 "too many `#` symbols: raw byte strings may be \
+                                                  delimited by up to 65535 `#` symbols");
+                self.fatal_errs.push(err);
+                return Err(());
             }
             self.bump();
             hash_count += 1;
         }
 
         if self.is_eof() {
-            self.fail_unterminated_raw_string(start_bpos, hash_count);
+            self.fail_unterminated_raw_string(start_bpos, hash_count, None)?;
+            return Ok(token::ByteStrRaw(self.intern(""), hash_count));
+        } else if hash_count == 1 && ident_start(self.ch) {
+            // `br#foo`, as if there were byte raw identifiers
+            while ident_continue(self.ch) {
+                self.bump();
+            }
+            let pos = self.pos;
+            let err = self.struct_span_fatal(start_bpos - BytePos(1),
+                                             pos,
+                                             "byte raw identifiers are not valid; did you mean \
+                                              a byte raw string `br\"...\"`?");
+            self.fatal_errs.push(err);
+            return Err(());
         } else if !self.ch_is('"') {
             let pos = self.pos;
             let ch = self.ch.unwrap();
-            self.fatal_span_char(start_bpos,
-                                        pos,
-                                        "found invalid character; only `#` is allowed in raw \
-                                         string delimitation",
-                                        ch).raise();
+            let err = self.struct_fatal_span_char(start_bpos,
+                                                  pos,
+                                                  "found invalid character; only `#` is allowed \
+                                                   in raw string delimitation",
+                                                  ch);
+            self.fatal_errs.push(err);
+            return Err(());
         }
         self.bump();
         let content_start_bpos = self.pos;
         let mut content_end_bpos;
+        let mut found_hashes = None;
+        let mut non_ascii = Vec::new();
         'outer: loop {
             match self.ch {
                 None => {
-                    self.fail_unterminated_raw_string(start_bpos, hash_count);
+                    self.report_non_ascii_raw_bytes(&non_ascii);
+                    self.fail_unterminated_raw_string(start_bpos, hash_count, found_hashes)?;
+                    let id = self.name_from(content_start_bpos);
+                    return Ok(token::ByteStrRaw(id, hash_count));
                 }
                 Some('"') => {
                     content_end_bpos = self.pos;
-                    for _ in 0..hash_count {
+                    for found in 0..hash_count {
                         self.bump();
                         if !self.ch_is('#') {
+                            found_hashes = found_hashes.max(Some(found));
                             continue 'outer;
                         }
                     }
@@ -1437,8 +2574,7 @@ impl<'a> StringReader<'a> {
                 }
                 Some(c) => {
                     if c > '\x7F' {
-                        let pos = self.pos;
-                        self.err_span_char(pos, pos, "raw byte string must be ASCII", c);
+                        non_ascii.push((self.pos, c));
                     }
                 }
             }
@@ -1447,7 +2583,41 @@ impl<'a> StringReader<'a> {
 
         self.bump();
 
-        token::ByteStrRaw(self.name_from_to(content_start_bpos, content_end_bpos), hash_count)
+        self.check_raw_string_indentation(content_start_bpos, content_end_bpos);
+        let id = if non_ascii.is_empty() {
+            self.name_from_to(content_start_bpos, content_end_bpos)
+        } else {
+            // keep the literal, with a placeholder byte for each bad character
+            self.report_non_ascii_raw_bytes(&non_ascii);
+            self.with_str_from_to(content_start_bpos, content_end_bpos, |s| {
+                let s = s.chars().map(|c| if c > '\x7F' { '?' } else { c }).collect::<String>();
+                self.intern(&s)
+            })
+        };
+        Ok(token::ByteStrRaw(id, hash_count))
+    }
+
+    /// Reports the non-ASCII characters of a raw byte string in a single error,
+    /// with a label for each if there are several.
+    fn report_non_ascii_raw_bytes(&self, non_ascii: &[(BytePos, char)]) {
+        let char_span = |&(pos, c): &(BytePos, char)| {
+            self.mk_sp(pos, pos + Pos::from_usize(c.len_utf8()))
+        };
+        if non_ascii.len() == 1 {
+            let (pos, c) = non_ascii[0];
+            let sp = char_span(&non_ascii[0]);
+            self.err_span_char(pos, sp.hi(), "raw byte string must be ASCII", c);
+        } else if !non_ascii.is_empty() {
+            let spans = non_ascii.iter().map(char_span).collect::<Vec<_>>();
+            let mut err = self.sess.span_diagnostic
+                .struct_span_err(spans.clone(), "raw byte string must be ASCII");
+            for (&sp, &(_, c)) in spans.iter().zip(non_ascii) {
+                let mut label = String::new();
+                push_escaped_char(&mut label, c);
+                err.span_label(sp, label);
+            }
+            err.emit();
+        }
     }
 
     fn validate_char_escape(&self, start_with_quote: BytePos) {
@@ -1567,6 +2737,16 @@ fn ident_continue(c: Option<char>) -> bool {
     (c > '\x7f' && c.is_xid_continue())
 }
 
+/// Whether two names are equal once lowercased, as for
+/// `StringReader::idents_equal_lowercased`.
+fn strs_equal_lowercased(a: &str, b: &str) -> bool {
+    if a.is_ascii() && b.is_ascii() {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a.chars().flat_map(char::to_lowercase).eq(b.chars().flat_map(char::to_lowercase))
+    }
+}
+
 #[inline]
 fn char_at(s: &str, byte: usize) -> char {
     s[byte..].chars().next().unwrap()
@@ -1582,9 +2762,12 @@ mod tests {
     use crate::feature_gate::UnstableFeatures;
     use crate::parse::token;
     use crate::diagnostics::plugin::ErrorMap;
+    use crate::early_buffered_lints::BufferedEarlyLintId;
+    use crate::test_snippet::Shared;
     use crate::with_globals;
     use std::io;
     use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
     use syntax_pos::{BytePos, Span, NO_EXPANSION};
     use rustc_data_structures::fx::{FxHashSet, FxHashMap};
     use rustc_data_structures::sync::Lock;
@@ -1606,6 +2789,7 @@ mod tests {
             registered_diagnostics: Lock::new(ErrorMap::new()),
             buffered_lints: Lock::new(vec![]),
             ambiguous_block_expr_parse: Lock::new(FxHashMap::default()),
+            max_literal_suffix_len: None,
         }
     }
 
@@ -1615,7 +2799,22 @@ mod tests {
                  teststr: String)
                  -> StringReader<'a> {
         let sf = sm.new_source_file(PathBuf::from(teststr.clone()).into(), teststr);
-        let mut sr = StringReader::new_raw(sess, sf, None);
+        let mut sr = StringReader::new_raw(sess, sf, None, LexerOptions::default());
+        if sr.advance_token().is_err() {
+            sr.emit_fatal_errors();
+            FatalError.raise();
+        }
+        sr
+    }
+
+    // like `setup`, but with the style lints turned on
+    fn setup_linting<'a>(sm: &SourceMap,
+                         sess: &'a ParseSess,
+                         teststr: String)
+                         -> StringReader<'a> {
+        let sf = sm.new_source_file(PathBuf::from(teststr.clone()).into(), teststr);
+        let options = LexerOptions { style_lints: true, ..LexerOptions::default() };
+        let mut sr = StringReader::new_raw(sess, sf, None, options);
         if sr.advance_token().is_err() {
             sr.emit_fatal_errors();
             FatalError.raise();
@@ -1658,6 +2857,35 @@ mod tests {
         })
     }
 
+    // lex the whole input, discarding the tokens
+    fn lex_to_eof(mut string_reader: StringReader<'_>) {
+        while string_reader.next_token().tok != token::Eof {}
+    }
+
+    // lex the whole input, keeping every token
+    fn lex_all(mut string_reader: StringReader<'_>) -> Vec<TokenAndSpan> {
+        let mut tokens = Vec::new();
+        loop {
+            let t = string_reader.next_token();
+            if t.tok == token::Eof {
+                return tokens;
+            }
+            tokens.push(t);
+        }
+    }
+
+    // the spans and messages of the buffered lints with the given id
+    fn buffered_lints(sess: &ParseSess, lint_id: BufferedEarlyLintId) -> Vec<(Span, String)> {
+        sess.buffered_lints.borrow().iter()
+            .filter(|lint| lint.lint_id == lint_id)
+            .map(|lint| (lint.span.primary_span().unwrap(), lint.msg.clone()))
+            .collect()
+    }
+
+    fn mk_sp(lo: u32, hi: u32) -> Span {
+        Span::new(BytePos(lo), BytePos(hi), NO_EXPANSION)
+    }
+
     // check that the given reader produces the desired stream
     // of tokens (stop checking after exhausting the expected vec)
     fn check_tokenization(mut string_reader: StringReader<'_>, expected: Vec<token::Token>) {
@@ -1747,7 +2975,7 @@ mod tests {
             let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
             let sh = mk_sess(sm.clone());
             assert_eq!(setup(&sm, &sh, "'abc".to_string()).next_token().tok,
-                    token::Lifetime(Ident::from_str("'abc")));
+                    token::Lifetime(Ident::from_str("'abc"), false));
         })
     }
 
@@ -1838,4 +3066,1236 @@ mod tests {
                     token::DocComment(Symbol::intern("/// test")));
         })
     }
+
+    #[test]
+    fn scoped_interners() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lex_ident = |interner: &Lrc<Lock<Interner>>, src: &str| {
+                let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+                let options = LexerOptions {
+                    interner: Some(interner.clone()),
+                    ..LexerOptions::default()
+                };
+                let mut sr = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+                match sr.next_token().tok {
+                    token::Ident(ident, false) => ident.name,
+                    tok => panic!("expected an identifier, found {:?}", tok),
+                }
+            };
+
+            let first = Lrc::new(Lock::new(Interner::fresh()));
+            let second = Lrc::new(Lock::new(Interner::fresh()));
+            second.lock().intern("bar");
+            let first_foo = lex_ident(&first, "foo");
+            let second_foo = lex_ident(&second, "foo");
+            // `foo` is the first symbol added to `first` but the second one added
+            // to `second`. Had both readers interned into the same interner, be
+            // it the global one, they would have produced the same symbol.
+            assert_ne!(first_foo, second_foo);
+            assert_eq!(first.lock().get(first_foo), "foo");
+            assert_eq!(second.lock().get(second_foo), "foo");
+            assert_eq!(first.lock().intern("foo"), first_foo);
+            assert_eq!(second.lock().intern("foo"), second_foo);
+        })
+    }
+
+    #[test]
+    fn idents_equal_lowercased_with_a_scoped_interner() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = "Foo foo Bar";
+            let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+            let interner = Lrc::new(Lock::new(Interner::fresh()));
+            let options = LexerOptions {
+                interner: Some(interner.clone()),
+                ..LexerOptions::default()
+            };
+            let mut lexer = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+            let mut names = Vec::new();
+            loop {
+                match lexer.next_token().tok {
+                    token::Ident(ident, false) => names.push(ident.name),
+                    token::Eof => break,
+                    _ => {}
+                }
+            }
+            assert_eq!(names.len(), 3);
+            assert_eq!(interner.lock().get(names[0]), "Foo");
+
+            // The symbols mean something else, if anything, to the global interner,
+            // so they must be compared through the reader that produced them.
+            assert!(lexer.idents_equal_lowercased(names[0], names[1]));
+            assert!(!lexer.idents_equal_lowercased(names[0], names[2]));
+        })
+    }
+
+    #[test]
+    fn last_token_len() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup(&sm, &sh, "  foobar ".to_string());
+            assert_eq!(lexer.last_token_len(), 0);
+            assert_eq!(lexer.real_token().tok, mk_ident("foobar"));
+            assert_eq!(lexer.last_token_len(), 6);
+            assert_eq!(lexer.real_token().tok, token::Eof);
+            assert_eq!(lexer.last_token_len(), 0);
+        })
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            lex_to_eof(setup_linting(&sm, &sh, "let x = 1; \t\r\n  let y;\n\nz  ".to_string()));
+            assert_eq!(buffered_lints(&sh, BufferedEarlyLintId::TrailingWhitespace),
+                       vec![(mk_sp(10, 12), "trailing whitespace".to_string()),
+                            (mk_sp(25, 27), "trailing whitespace".to_string())]);
+        })
+    }
+
+    #[test]
+    fn trailing_whitespace_on_blank_lines() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            lex_to_eof(setup_linting(&sm, &sh, "  let x = 1;\r\n    \n// c\r\n\t".to_string()));
+            assert_eq!(buffered_lints(&sh, BufferedEarlyLintId::TrailingWhitespace),
+                       vec![(mk_sp(14, 18), "trailing whitespace".to_string()),
+                            (mk_sp(25, 26), "trailing whitespace".to_string())]);
+        })
+    }
+
+    #[test]
+    fn style_lints_are_off_by_default() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = "let x = 1.foo(); \n/// a\tb\r\nr\"c\" 0755 \"\n\";\n    \n}";
+            lex_to_eof(setup(&sm, &sh, src.to_string()));
+            assert!(sh.buffered_lints.borrow().is_empty());
+
+            lex_to_eof(setup_linting(&sm, &sh, src.to_string()));
+            for lint_id in vec![BufferedEarlyLintId::TrailingWhitespace,
+                                BufferedEarlyLintId::TabsInDocComments,
+                                BufferedEarlyLintId::MissingFinalNewline,
+                                BufferedEarlyLintId::IntegerLiteralMethodCall,
+                                BufferedEarlyLintId::UnnecessaryRawStrings,
+                                BufferedEarlyLintId::CStyleOctalLiterals,
+                                BufferedEarlyLintId::MixedLineEndings,
+                                BufferedEarlyLintId::OddQuotesOnLine] {
+                assert!(!buffered_lints(&sh, lint_id).is_empty());
+            }
+        })
+    }
+
+    #[test]
+    fn trailing_whitespace_in_line_comments() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            lex_to_eof(setup_linting(&sm, &sh, "x; // c  \n/// d\t\r\n//\n// e ".to_string()));
+            assert_eq!(buffered_lints(&sh, BufferedEarlyLintId::TrailingWhitespace),
+                       vec![(mk_sp(7, 9), "trailing whitespace".to_string()),
+                            (mk_sp(15, 16), "trailing whitespace".to_string()),
+                            (mk_sp(25, 26), "trailing whitespace".to_string())]);
+        })
+    }
+
+    #[test]
+    fn positions_in_trivia_or_literals() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = "/* note */ let s = \"text\"; // done";
+            let tokens = LexedTokens::new(lex_all(setup(&sm, &sh, src.to_string())));
+            let pos_of = |needle: &str| BytePos(src.find(needle).unwrap() as u32);
+            assert!(tokens.is_in_trivia_or_literal(pos_of("note")));
+            assert!(tokens.is_in_trivia_or_literal(pos_of("text")));
+            assert!(tokens.is_in_trivia_or_literal(pos_of("done")));
+            assert!(!tokens.is_in_trivia_or_literal(pos_of("let")));
+            assert!(!tokens.is_in_trivia_or_literal(pos_of(" s ")));
+            assert!(!tokens.is_in_trivia_or_literal(pos_of(";")));
+            assert!(!tokens.is_in_trivia_or_literal(BytePos(src.len() as u32)));
+            assert!(!tokens.is_in_trivia_or_literal(BytePos(src.len() as u32 + 10)));
+        })
+    }
+
+    #[test]
+    fn prev_significant() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = "a /* one */ // two\n /// doc\n b";
+            let tokens = LexedTokens::new(lex_all(setup(&sm, &sh, src.to_string())));
+            let b = tokens.tokens.len() - 1;
+            assert_eq!(tokens.tokens[b].tok, mk_ident("b"));
+            let doc = tokens.prev_significant(b).unwrap();
+            assert_eq!(tokens.tokens[doc].tok, token::DocComment(Symbol::intern("/// doc")));
+            assert_eq!(tokens.prev_significant(doc), Some(0));
+            assert_eq!(tokens.prev_significant(0), None);
+            assert_eq!(tokens.prev_significant(tokens.tokens.len()), Some(b));
+            assert_eq!(tokens.prev_significant(tokens.tokens.len() + 1), None);
+        })
+    }
+
+    #[test]
+    fn split_shr() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            check_tokenization(setup(&sm, &sh, ">>".to_string()),
+                               vec![token::BinOp(token::Shr), token::Eof]);
+
+            let sf = sm.new_source_file(PathBuf::from("split").into(), ">>".to_string());
+            let options = LexerOptions { split_shr: true, ..LexerOptions::default() };
+            let mut lexer = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+            check_tokenization(lexer, vec![token::Gt, token::Gt, token::Eof]);
+        })
+    }
+
+    #[test]
+    fn text_between_tokens() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup(&sm, &sh, "foo /* c */ bar".to_string());
+            let foo = lexer.real_token();
+            let bar = lexer.real_token();
+            assert_eq!(bar.tok, mk_ident("bar"));
+            assert_eq!(lexer.text_between(&foo, &bar), Some(" /* c */ "));
+            assert_eq!(lexer.text_between(&bar, &foo), None);
+
+            let sf = sm.new_source_file(PathBuf::from("override").into(), "a b".to_string());
+            let mut lexer = StringReader::new_raw(&sh, sf, Some(mk_sp(0, 3)),
+                                                  LexerOptions::default());
+            lexer.advance_token().unwrap();
+            let a = lexer.real_token();
+            let b = lexer.real_token();
+            assert_eq!(b.tok, mk_ident("b"));
+            assert_eq!(lexer.text_between(&a, &b), None);
+        })
+    }
+
+    #[test]
+    fn max_nesting_depth() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup(&sm, &sh, "{{{}}} ([])".to_string());
+            lexer.real_token();
+            assert!(lexer.parse_all_token_trees().is_ok());
+            assert_eq!(lexer.max_nesting_depth(), 3);
+        })
+    }
+
+    #[test]
+    fn non_decimal_float_literals() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            for (i, lit) in ["0b1.0", "0o7.5", "0x1.8", "0o7.5f32"].iter().enumerate() {
+                let tok = setup(&sm, &sh, lit.to_string()).next_token().tok;
+                let name = Symbol::intern(lit.trim_end_matches("f32"));
+                assert_eq!(tok, token::Literal(token::Err(name), None));
+                assert_eq!(sh.span_diagnostic.err_count(), i + 1);
+            }
+        })
+    }
+
+    #[test]
+    fn integer_literal_method_call() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            lex_to_eof(setup_linting(&sm, &sh, "1.foo(); 2.0; 3..4; t.0.bar".to_string()));
+            assert_eq!(
+                buffered_lints(&sh, BufferedEarlyLintId::IntegerLiteralMethodCall),
+                vec![(mk_sp(0, 1),
+                      "this accesses a member of the integer `1`, not of the float `1.0`"
+                          .to_string())]
+            );
+        })
+    }
+
+    #[test]
+    fn digit_separators() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lex_strict = |src: &str| {
+                let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+                let options = LexerOptions {
+                    strict_digit_separators: true,
+                    ..LexerOptions::default()
+                };
+                let mut lexer = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+                lexer.next_token().tok
+            };
+
+            lex_to_eof(setup(&sm, &sh, "0x_FF 1_000_000 1_.0".to_string()));
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+
+            let million = token::Literal(token::Integer(Symbol::intern("1_000_000")), None);
+            assert_eq!(lex_strict("1_000_000"), million);
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+
+            let hex = token::Literal(token::Integer(Symbol::intern("0x_FF")), None);
+            assert_eq!(lex_strict("0x_FF"), hex);
+            assert_eq!(sh.span_diagnostic.err_count(), 1);
+
+            let float = token::Literal(token::Float(Symbol::intern("1_.0")), None);
+            assert_eq!(lex_strict("1_.0"), float);
+            assert_eq!(sh.span_diagnostic.err_count(), 2);
+
+            lex_to_eof(setup(&sm, &sh, "1__0".to_string()));
+            assert_eq!(sh.span_diagnostic.err_count(), 2);
+
+            let repeated = token::Literal(token::Integer(Symbol::intern("1__0")), None);
+            assert_eq!(lex_strict("1__0"), repeated);
+            assert_eq!(sh.span_diagnostic.err_count(), 3);
+
+            let repeated = token::Literal(token::Integer(Symbol::intern("0x1___0__0")), None);
+            assert_eq!(lex_strict("0x1___0__0"), repeated);
+            assert_eq!(sh.span_diagnostic.err_count(), 4);
+        })
+    }
+
+    #[test]
+    fn tokenize_to_vec() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let tokens = setup(&sm, &sh, "// c\nfoo /* b */ bar".to_string())
+                .tokenize_to_vec()
+                .unwrap();
+            assert_eq!(tokens.into_iter().map(|t| t.tok).collect::<Vec<_>>(),
+                       vec![token::Comment, token::Whitespace, mk_ident("foo"),
+                            token::Whitespace, token::Comment, token::Whitespace,
+                            mk_ident("bar")]);
+
+            let errs = setup(&sm, &sh, "// c\nfoo /* b */ \"bar".to_string())
+                .tokenize_to_vec()
+                .unwrap_err();
+            assert_eq!(errs.iter().map(|d| d.message()).collect::<Vec<_>>(),
+                       vec!["unterminated double quote string".to_string()]);
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn tokenize_to_vec_returns_every_fatal_error() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let invalid_delim = "found invalid character; only `#` is allowed in raw string \
+                                 delimitation: x";
+            let too_many_hashes = format!("r{}\"\"", "#".repeat(65536));
+            let too_many_byte_hashes = format!("b{}", too_many_hashes);
+            let cases = [
+                ("'=x", "unterminated character literal"),
+                ("b'=x", "unterminated byte constant"),
+                ("r#\"abc", "unterminated raw string"),
+                ("br\"abc", "unterminated raw string"),
+                ("r##x", invalid_delim),
+                ("br##x", invalid_delim),
+                ("br#foo", "byte raw identifiers are not valid; did you mean a byte raw \
+                            string `br\"...\"`?"),
+                (&too_many_hashes[..], "too many `#` symbols: raw strings may be delimited by up \
+                                    to 65535 `#` symbols"),
+                (&too_many_byte_hashes[..], "too many `#` symbols: raw byte strings may be \
+                                         delimited by up to 65535 `#` symbols"),
+            ];
+            for &(src, msg) in cases.iter() {
+                let errs = setup(&sm, &sh, src.to_string()).tokenize_to_vec().unwrap_err();
+                assert_eq!(errs.iter().map(|d| d.message()).collect::<Vec<_>>(),
+                           vec![msg.to_string()],
+                           "lexing {:?}", src);
+            }
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn missing_final_newline() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            lex_to_eof(setup_linting(&sm, &sh, "fn main() {}\n".to_string()));
+            assert!(buffered_lints(&sh, BufferedEarlyLintId::MissingFinalNewline).is_empty());
+
+            lex_to_eof(setup_linting(&sm, &sh, "fn main() {}".to_string()));
+            let lints = buffered_lints(&sh, BufferedEarlyLintId::MissingFinalNewline);
+            assert_eq!(lints.len(), 1);
+            assert_eq!(sm.span_to_snippet(lints[0].0).unwrap(), "}");
+            assert_eq!(lints[0].1, "missing newline at end of file");
+        })
+    }
+
+    #[test]
+    fn hex_float_literals() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lex = |src: &str| {
+                let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+                let options = LexerOptions {
+                    hex_float_literals: true,
+                    ..LexerOptions::default()
+                };
+                let mut sr = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+                sr.next_token().tok
+            };
+            for lit in &["0x1p4", "0x1.8p-2", "0xA_Bp+1"] {
+                assert_eq!(lex(lit), token::Literal(token::Float(Symbol::intern(lit)), None));
+            }
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+
+            // without the option, the `p4` is a suffix
+            let tok = setup(&sm, &sh, "0x1p4".to_string()).next_token().tok;
+            assert_eq!(tok, token::Literal(token::Integer(Symbol::intern("0x1")),
+                                           Some(Symbol::intern("p4"))));
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+
+            let tok = lex("0x1.8");
+            assert_eq!(tok, token::Literal(token::Err(Symbol::intern("0x1.8")), None));
+            assert_eq!(sh.span_diagnostic.err_count(), 1);
+
+            let tok = lex("0x1.8p;");
+            assert_eq!(tok, token::Literal(token::Float(Symbol::intern("0x1.8p")), None));
+            assert_eq!(sh.span_diagnostic.err_count(), 2);
+        })
+    }
+
+    #[test]
+    fn hex_float_fraction_starting_with_a_letter() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lex = |src: &str| {
+                let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+                let options = LexerOptions {
+                    hex_float_literals: true,
+                    ..LexerOptions::default()
+                };
+                let sr = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+                lex_all(sr).into_iter().map(|t| t.tok).collect::<Vec<_>>()
+            };
+            for lit in &["0x1.Ap3", "0x0.fp0", "0x1.p4", "0x1.P-4"] {
+                assert_eq!(lex(lit), vec![token::Literal(token::Float(Symbol::intern(lit)), None)]);
+            }
+
+            // without a binary exponent, the `.` is a field access or method call
+            let int = |s| token::Literal(token::Integer(Symbol::intern(s)), None);
+            assert_eq!(lex("0xff.abs"), vec![int("0xff"), token::Dot, mk_ident("abs")]);
+            assert_eq!(lex("0x1.pow"), vec![int("0x1"), token::Dot, mk_ident("pow")]);
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn hex_integer_with_p_suffix() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let expected = token::Literal(token::Integer(Symbol::intern("0x10")),
+                                          Some(Symbol::intern("px")));
+            let tok = setup(&sm, &sh, "0x10px".to_string()).next_token().tok;
+            assert_eq!(tok, expected);
+
+            let sf = sm.new_source_file(PathBuf::from("px").into(), "0x10px".to_string());
+            let options = LexerOptions {
+                hex_float_literals: true,
+                ..LexerOptions::default()
+            };
+            let mut sr = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+            assert_eq!(sr.next_token().tok, expected);
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn peek_n() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let toks = |ts: Vec<TokenAndSpan>| ts.into_iter().map(|t| t.tok).collect::<Vec<_>>();
+            let mut lexer = setup(&sm, &sh, "a b c".to_string());
+
+            assert_eq!(toks(lexer.peek_n(3).unwrap()),
+                       vec![mk_ident("a"), token::Whitespace, mk_ident("b")]);
+            assert_eq!(lexer.next_token().tok, mk_ident("a"));
+            assert_eq!(toks(lexer.peek_n(3).unwrap()),
+                       vec![token::Whitespace, mk_ident("b"), token::Whitespace]);
+            assert_eq!(toks(lexer.peek_n(1).unwrap()), vec![token::Whitespace]);
+            assert_eq!(toks(lexer.peek_n(10).unwrap()),
+                       vec![token::Whitespace, mk_ident("b"), token::Whitespace,
+                            mk_ident("c"), token::Eof]);
+
+            assert_eq!(toks(lex_all(lexer)),
+                       vec![token::Whitespace, mk_ident("b"), token::Whitespace, mk_ident("c")]);
+        })
+    }
+
+    #[test]
+    fn peek_n_keeps_position() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup(&sm, &sh, "a\nb\nc".to_string());
+            let before = lexer.current_line_col();
+            assert_eq!(lexer.peek_n(2).unwrap().len(), 2);
+            assert_eq!(lexer.current_line_col(), before);
+            assert_eq!(lexer.peek_n(4).unwrap().len(), 4);
+            assert_eq!(lexer.current_line_col(), before);
+
+            // consuming the tokens lexed ahead moves the position as usual
+            lexer.next_token();
+            assert_eq!(lexer.current_line_col(), (2, 0));
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert_eq!(lexer.next_token().tok, mk_ident("b"));
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert_eq!(lexer.next_token().tok, mk_ident("c"));
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+
+            // a fatal error while lexing ahead is returned, and leaves the position
+            let mut lexer = setup(&sm, &sh, "a '=x".to_string());
+            let before = lexer.current_line_col();
+            assert!(lexer.peek_n(3).is_err());
+            assert_eq!(lexer.current_line_col(), before);
+            let errs = lexer.buffer_fatal_errors();
+            assert_eq!(errs.iter().map(|d| d.message()).collect::<Vec<_>>(),
+                       vec!["unterminated character literal".to_string()]);
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn unterminated_block_comment() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let snippet = |sp: Span| sm.span_to_snippet(sp).unwrap();
+            let pos = |sp: Span| sm.lookup_char_pos(sp.lo()).col.to_usize();
+            let lex_errs = |src: &str| {
+                let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+                let mut lexer = StringReader::new_raw(&sh, sf, None, LexerOptions::default());
+                assert!(lexer.advance_token().is_err());
+                lexer.buffer_fatal_errors()
+            };
+
+            let errs = lex_errs("/* a /* b */");
+            assert_eq!(errs.len(), 1);
+            let primary = errs[0].span.primary_span().unwrap();
+            assert_eq!((snippet(primary), pos(primary)), ("/*".to_string(), 0));
+            assert!(errs[0].children.is_empty());
+
+            let errs = lex_errs("/* a /* b /* c */");
+            assert_eq!(errs[0].message(), "unterminated block comment");
+            assert_eq!(pos(errs[0].span.primary_span().unwrap()), 5);
+            let notes = errs[0].children.iter()
+                .map(|note| pos(note.span.primary_span().unwrap()))
+                .collect::<Vec<_>>();
+            assert_eq!(notes, vec![0]);
+        })
+    }
+
+    #[test]
+    fn c_style_octal_literals() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let tokens = lex_all(setup_linting(&sm, &sh, "0755 0899 0.5 0 00".to_string()));
+            assert_eq!(tokens[4].tok, token::Literal(token::Float(Symbol::intern("0.5")), None));
+            assert_eq!(tokens[6].tok, token::Literal(token::Integer(Symbol::intern("0")), None));
+            assert_eq!(
+                buffered_lints(&sh, BufferedEarlyLintId::CStyleOctalLiterals),
+                vec![
+                    (mk_sp(0, 4),
+                     "`0755` is a decimal literal, not an octal one; write `0o755` for octal"
+                         .to_string()),
+                    (mk_sp(5, 9),
+                     "`0899` is a decimal literal with a leading zero, \
+                      and has an invalid digit for octal".to_string()),
+                ]
+            );
+        })
+    }
+
+    #[test]
+    fn current_line_col() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup(&sm, &sh, "ab\n\té y".to_string());
+            let mut positions = vec![lexer.current_line_col()];
+            for _ in 0..3 {
+                lexer.next_token();
+                positions.push(lexer.current_line_col());
+            }
+            assert_eq!(positions, vec![(1, 2), (2, 1), (2, 2), (2, 3)]);
+        })
+    }
+
+    #[test]
+    fn unnecessary_raw_strings() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = r##"r"plain" r"has\backslash" r#"quote""#"##;
+            lex_to_eof(setup_linting(&sm, &sh, src.to_string()));
+            assert_eq!(
+                buffered_lints(&sh, BufferedEarlyLintId::UnnecessaryRawStrings),
+                vec![(mk_sp(0, 8),
+                      "raw string literal has no backslashes, so it can be a normal string literal"
+                          .to_string())]
+            );
+        })
+    }
+
+    #[test]
+    fn raw_lifetimes() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let tok = setup(&sm, &sh, "'r#fn".to_string()).next_token();
+            assert_eq!(tok.tok, token::Lifetime(Ident::from_str("'fn"), true));
+            assert_eq!(*sh.raw_identifier_spans.borrow(), vec![tok.sp]);
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+
+            let tok = setup(&sm, &sh, "'r#self".to_string()).next_token().tok;
+            assert_eq!(tok, token::Lifetime(Ident::from_str("'self"), true));
+            assert_eq!(sh.span_diagnostic.err_count(), 1);
+        })
+    }
+
+    #[test]
+    fn balanced_delimiters() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let balanced = |src: &str| is_balanced(&lex_all(setup(&sm, &sh, src.to_string())));
+            assert!(balanced("( [ ] )"));
+            assert!(balanced("a { b } c"));
+            assert!(!balanced("( ]"));
+            assert!(!balanced("( ("));
+            assert!(!balanced(")"));
+        })
+    }
+
+    #[test]
+    fn exponent_or_suffix() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lit = |src: &str| setup(&sm, &sh, src.to_string()).next_token().tok;
+            let sym = Symbol::intern;
+            assert_eq!(lit("1ex"), token::Literal(token::Integer(sym("1")), Some(sym("ex"))));
+            assert_eq!(lit("1.5Em"), token::Literal(token::Float(sym("1.5")), Some(sym("Em"))));
+            assert_eq!(lit("1e5"), token::Literal(token::Float(sym("1e5")), None));
+            assert_eq!(lit("1e+5f64"), token::Literal(token::Float(sym("1e+5")), Some(sym("f64"))));
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn recover_unterminated_raw_strings() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = "r#\"abc".to_string();
+            let sf = sm.new_source_file(PathBuf::from("raw").into(), src);
+            let options = LexerOptions { recover_raw_strings: true, ..LexerOptions::default() };
+            let mut lexer = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+            assert_eq!(lexer.peek().tok,
+                       token::Literal(token::StrRaw(Symbol::intern("abc"), 1), None));
+            assert_eq!(lexer.peek().sp, Span::new(BytePos(0), BytePos(6), NO_EXPANSION));
+
+            let errs = lexer.take_recovered_errors();
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[0].message(), "unterminated raw string");
+            assert!(lexer.take_recovered_errors().is_empty());
+            assert_eq!(lexer.next_token().tok,
+                       token::Literal(token::StrRaw(Symbol::intern("abc"), 1), None));
+            assert_eq!(lexer.next_token().tok, token::Eof);
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn whitespace_text() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = "a \t b\n\n\tc".to_string();
+            let sf = sm.new_source_file(PathBuf::from("ws").into(), src);
+            let options = LexerOptions { keep_whitespace_text: true, ..LexerOptions::default() };
+            let mut lexer = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+            let texts = lexer.tokenize_to_vec().unwrap().iter()
+                .map(|t| lexer.whitespace_text(t).map(|s| s.to_string()))
+                .collect::<Vec<_>>();
+            assert_eq!(texts, vec![None, Some(" \t ".to_string()), None,
+                                   Some("\n\n\t".to_string()), None]);
+
+            let mut lexer = setup(&sm, &sh, "a b".to_string());
+            let ws = lexer.tokenize_to_vec().unwrap().remove(1);
+            assert_eq!(ws.tok, token::Whitespace);
+            assert_eq!(lexer.whitespace_text(&ws), None);
+        })
+    }
+
+    #[test]
+    fn confusable_digits() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup(&sm, &sh, "1\u{ff12}3 + 4".to_string());
+            assert_eq!(lexer.next_token().tok,
+                       token::Literal(token::Err(Symbol::intern("1\u{ff12}3")), None));
+            assert_eq!(sh.span_diagnostic.err_count(), 1);
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert_eq!(lexer.next_token().tok, token::BinOp(token::Plus));
+
+            for (i, lit) in ["1e\u{ff13}", "1.5e-\u{ff13}", "2E1\u{ff13}"].iter().enumerate() {
+                let tok = setup(&sm, &sh, lit.to_string()).next_token().tok;
+                assert_eq!(tok, token::Literal(token::Err(Symbol::intern(lit)), None));
+                assert_eq!(sh.span_diagnostic.err_count(), i + 2);
+            }
+
+            for lit in &["7\u{660}", "4\u{667}2", "1\u{6f9}"] {
+                let tok = setup(&sm, &sh, lit.to_string()).next_token().tok;
+                assert_eq!(tok, token::Literal(token::Err(Symbol::intern(lit)), None));
+            }
+            assert_eq!(sh.span_diagnostic.err_count(), 7);
+
+            let src = "\u{ff10}".to_string();
+            let sf = sm.new_source_file(PathBuf::from("zero").into(), src);
+            let mut lexer = StringReader::new_raw(&sh, sf, None, LexerOptions::default());
+            assert!(lexer.advance_token().is_err());
+            let errs = lexer.buffer_fatal_errors();
+            assert_eq!(errs[0].message(), "unknown start of token: \\u{ff10}");
+            assert_eq!(errs[0].suggestions[0].msg,
+                       "Unicode character '\u{ff10}' (Fullwidth Digit Zero) looks like '0' \
+                        (Digit Zero), but it is not");
+
+            let src = "\u{6f8}".to_string();
+            let sf = sm.new_source_file(PathBuf::from("eight").into(), src);
+            let mut lexer = StringReader::new_raw(&sh, sf, None, LexerOptions::default());
+            assert!(lexer.advance_token().is_err());
+            let errs = lexer.buffer_fatal_errors();
+            assert_eq!(errs[0].suggestions[0].msg,
+                       "Unicode character '\u{6f8}' (Extended Arabic-Indic Digit Eight) looks \
+                        like '8' (Digit Eight), but it is not");
+        })
+    }
+
+    #[test]
+    fn src_text() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = "let r#x = /* a\n b */ r##\"q\"#\"##; // c";
+            let mut lexer = setup(&sm, &sh, src.to_string());
+            let tokens = lexer.tokenize_to_vec().unwrap();
+            let texts = tokens.iter().map(|t| lexer.src_text(t).unwrap()).collect::<Vec<_>>();
+            assert_eq!(texts, vec!["let", " ", "r#x", " ", "=", " ", "/* a\n b */", " ",
+                                   "r##\"q\"#\"##", ";", " ", "// c"]);
+            assert_eq!(texts.concat(), src);
+            assert_eq!(lexer.src_text(&lexer.peek()), Some(""));
+
+            let sf = sm.new_source_file(PathBuf::from("override").into(), "a b".to_string());
+            let mut lexer = StringReader::new_raw(&sh, sf, Some(mk_sp(0, 3)),
+                                                  LexerOptions::default());
+            lexer.advance_token().unwrap();
+            let a = lexer.next_token();
+            assert_eq!(a.tok, mk_ident("a"));
+            assert_eq!(lexer.src_text(&a), None);
+            assert_eq!(lexer.src_text(&lexer.peek()), None);
+        })
+    }
+
+    #[test]
+    fn tabs_in_doc_comments() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = "/// a\tb\t\n// c\td\n/** e\tf */\n/* g\th */\n".to_string();
+            lex_to_eof(setup_linting(&sm, &sh, src));
+            let msg = "tab character in doc comment".to_string();
+            assert_eq!(buffered_lints(&sh, BufferedEarlyLintId::TabsInDocComments),
+                       vec![(mk_sp(5, 6), msg.clone()), (mk_sp(21, 22), msg)]);
+        })
+    }
+
+    #[test]
+    fn tokens_with_lines() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup(&sm, &sh, "let x = 1;\n  x".to_string());
+            let lines = lexer.tokens_with_lines()
+                .map(|(line, t)| (line, t.tok))
+                .collect::<Vec<_>>();
+            assert_eq!(lines, vec![(1, mk_ident("let")),
+                                   (1, mk_ident("x")),
+                                   (1, token::Eq),
+                                   (1, token::Literal(token::Integer(Symbol::intern("1")), None)),
+                                   (1, token::Semi),
+                                   (2, mk_ident("x"))]);
+        })
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            for src in &[r"'\u{1F600}'", r"'\u{1f600}'", r#""\u{1F600}\u{1f600}""#] {
+                lex_to_eof(setup(&sm, &sh, src.to_string()));
+            }
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+
+            let malformed = [r"'\u{1F600'", r#""\u{1F600""#, r"b'\u{7F'", r#"b"\u{7F""#];
+            for (i, src) in malformed.iter().enumerate() {
+                lex_to_eof(setup(&sm, &sh, src.to_string()));
+                assert_eq!(sh.span_diagnostic.err_count(), i + 1);
+            }
+        })
+    }
+
+    #[test]
+    fn rational_literals() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lex = |src: &str| {
+                let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+                let options = LexerOptions { rational_literals: true, ..LexerOptions::default() };
+                let sr = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+                lex_all(sr).into_iter().map(|t| (t.tok, t.sp)).collect::<Vec<_>>()
+            };
+            let frac = |n, d| token::Literal(token::Frac(Symbol::intern(n), Symbol::intern(d)),
+                                             None);
+            let int = |s| token::Literal(token::Integer(Symbol::intern(s)), None);
+
+            assert_eq!(lex("3/4r"), vec![(frac("3", "4"), mk_sp(0, 4))]);
+            assert_eq!(lex("10/2r+1"), vec![(frac("10", "2"), mk_sp(0, 5)),
+                                            (token::BinOp(token::Plus), mk_sp(5, 6)),
+                                            (int("1"), mk_sp(6, 7))]);
+            assert_eq!(lex("3/4"), vec![(int("3"), mk_sp(0, 1)),
+                                        (token::BinOp(token::Slash), mk_sp(1, 2)),
+                                        (int("4"), mk_sp(2, 3))]);
+            let toks = lex("3/4rx").into_iter().map(|(tok, _)| tok).collect::<Vec<_>>();
+            assert_eq!(toks, vec![int("3"), token::BinOp(token::Slash),
+                                  token::Literal(token::Integer(Symbol::intern("4")),
+                                                 Some(Symbol::intern("rx")))]);
+
+            // without the option, `3/4r` is a division by a suffixed integer
+            let mut lexer = setup(&sm, &sh, "3/4r".to_string());
+            assert_eq!(lexer.next_token().tok, int("3"));
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn retokenize_verbose() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            sm.new_source_file(PathBuf::from("retok").into(), "x = a  +  b;".to_string());
+            let tokens = StringReader::retokenize_verbose(&sh, mk_sp(4, 11)).unwrap();
+            let tokens = tokens.into_iter().map(|t| (t.tok, t.sp)).collect::<Vec<_>>();
+            assert_eq!(tokens, vec![(mk_ident("a"), mk_sp(4, 5)),
+                                    (token::Whitespace, mk_sp(5, 7)),
+                                    (token::BinOp(token::Plus), mk_sp(7, 8)),
+                                    (token::Whitespace, mk_sp(8, 10)),
+                                    (mk_ident("b"), mk_sp(10, 11))]);
+        })
+    }
+
+    #[test]
+    fn lone_quote() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lex_errs = |src: &str| {
+                let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+                let mut lexer = StringReader::new_raw(&sh, sf, None, LexerOptions::default());
+                assert!(lexer.advance_token().is_err());
+                lexer.buffer_fatal_errors()
+            };
+
+            let errs = lex_errs("' b");
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[0].message(), "lone `'` in source");
+            assert_eq!(errs[0].span.primary_span(), Some(mk_sp(0, 1)));
+
+            let errs = lex_errs("' \n");
+            assert_eq!(errs[0].message(), "lone `'` in source");
+
+            let mut lexer = setup(&sm, &sh, "'x y ' '".to_string());
+            assert_eq!(lexer.next_token().tok, token::Lifetime(Ident::from_str("'x"), false));
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert_eq!(lexer.next_token().tok, mk_ident("y"));
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert_eq!(lexer.next_token().tok,
+                       token::Literal(token::Char(Symbol::intern(" ")), None));
+        })
+    }
+
+    #[test]
+    fn max_comment_nesting() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let src = format!("{}{}", "/*".repeat(2000), "*/".repeat(2000));
+            let sf = sm.new_source_file(PathBuf::from("nested").into(), src.clone());
+            let options = LexerOptions { max_comment_nesting: 64, ..LexerOptions::default() };
+            let errs = StringReader::new_with_options(&sh, sf, None, options).err().unwrap();
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[0].message(), "block comment nested too deeply");
+            assert_eq!(errs[0].span.primary_span(), Some(mk_sp(128, 130)));
+
+            // The default limit leaves deeply nested comments alone.
+            let mut lexer = setup(&sm, &sh, src);
+            assert_eq!(lexer.next_token().tok, token::Comment);
+            assert_eq!(lexer.next_token().tok, token::Eof);
+        })
+    }
+
+    #[test]
+    fn max_comment_nesting_boundaries() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            // the span of the `/*` rejected under `max`, if any
+            let rejected = |src: &str, max: usize| {
+                let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+                let options = LexerOptions { max_comment_nesting: max, ..LexerOptions::default() };
+                match StringReader::new_with_options(&sh, sf, None, options) {
+                    Ok(mut lexer) => {
+                        assert_eq!(lexer.next_token().tok, token::Comment);
+                        None
+                    }
+                    Err(errs) => {
+                        assert_eq!(errs.len(), 1);
+                        assert_eq!(errs[0].message(), "block comment nested too deeply");
+                        let sp = errs[0].span.primary_span().unwrap();
+                        Some(sm.lookup_char_pos(sp.lo()).col.to_usize())
+                    }
+                }
+            };
+
+            // A limit of 0 allows no block comment at all, doc comments included.
+            assert_eq!(rejected("/* a */", 0), Some(0));
+            assert_eq!(rejected("/** a */", 0), Some(0));
+
+            // A limit of 1 allows a comment but nothing nested in it.
+            assert_eq!(rejected("/* a */", 1), None);
+            assert_eq!(rejected("/* /* a */ */", 1), Some(3));
+
+            // A limit of N allows N comments open at once, but not N + 1.
+            assert_eq!(rejected("/* /* /* a */ */ */", 3), None);
+            assert_eq!(rejected("/* /* /* /* a */ */ */ */", 3), Some(9));
+            // The limit is on the depth, not on the number of nested comments.
+            assert_eq!(rejected("/* /* a */ /* b */ /* c */ /* d */ */", 2), None);
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn take_shebang() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+
+            let mut lexer = setup(&sm, &sh, "#!/bin/sh\nfn".to_string());
+            assert_eq!(lexer.take_shebang(), Some(("#!/bin/sh".to_string(), mk_sp(0, 9))));
+            assert_eq!(lexer.take_shebang(), None);
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert_eq!(lexer.next_token().tok, mk_ident("fn"));
+
+            let mut lexer = setup(&sm, &sh, "#![feature(x)]".to_string());
+            assert_eq!(lexer.take_shebang(), None);
+            assert_eq!(lexer.next_token().tok, token::Pound);
+            assert_eq!(lexer.next_token().tok, token::Not);
+
+            let mut lexer = setup(&sm, &sh, "fn main() {}".to_string());
+            assert_eq!(lexer.take_shebang(), None);
+            assert_eq!(lexer.next_token().tok, mk_ident("fn"));
+        })
+    }
+
+    #[test]
+    fn idents_equal_lowercased() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lexer = setup(&sm, &sh, "x".to_string());
+            let eq = |a, b| lexer.idents_equal_lowercased(Symbol::intern(a), Symbol::intern(b));
+            assert!(eq("Foo", "foo"));
+            assert!(eq("FOO_BAR", "foo_bar"));
+            assert!(!eq("foo", "bar"));
+            assert!(!eq("foo", "foo_"));
+            assert!(eq("Äpfel", "äPFEL"));
+            assert!(eq("ΔΈΛΤΑ", "δέλτα"));
+            assert!(!eq("äpfel", "apfel"));
+            assert!(!eq("STRASSE", "straße"));
+        })
+    }
+
+    #[test]
+    fn unterminated_raw_string_found_hashes() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let notes = |src: &str| {
+                let sf = sm.new_source_file(PathBuf::from(src).into(), src.to_string());
+                let options = LexerOptions { recover_raw_strings: true, ..LexerOptions::default() };
+                let mut lexer = StringReader::new_with_options(&sh, sf, None, options).unwrap();
+                let errs = lexer.take_recovered_errors();
+                assert_eq!(errs.len(), 1);
+                assert_eq!(errs[0].message(), "unterminated raw string");
+                errs[0].children.iter().map(|note| note.message()).collect::<Vec<_>>()
+            };
+
+            assert_eq!(notes("r###\"data\"##"),
+                       vec!["this raw string should be terminated with `\"###`",
+                            "found a closing sequence with 2 `#`, expected 3"]);
+            assert_eq!(notes("br###\"a\"# b\"## c\"#"),
+                       vec!["this raw string should be terminated with `\"###`",
+                            "found a closing sequence with 2 `#`, expected 3"]);
+            assert_eq!(notes("r#\"data"),
+                       vec!["this raw string should be terminated with `\"#`"]);
+        })
+    }
+
+    #[test]
+    fn separators_in_float_exponent() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let float = |s| token::Literal(token::Float(Symbol::intern(s)), None);
+
+            assert_eq!(setup(&sm, &sh, "1e_5".to_string()).next_token().tok, float("1e_5"));
+            assert_eq!(setup(&sm, &sh, "1e5_".to_string()).next_token().tok, float("1e5_"));
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+
+            assert_eq!(setup(&sm, &sh, "1e_".to_string()).next_token().tok, float("1e_"));
+            assert_eq!(sh.span_diagnostic.err_count(), 1);
+        })
+    }
+
+    #[test]
+    fn reset_to() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup(&sm, &sh, "fn f(x: u8) {}".to_string());
+            let lex4 = |lexer: &mut StringReader<'_>| {
+                (0..4).map(|_| {
+                    let t = lexer.next_token();
+                    (t.tok, t.sp)
+                }).collect::<Vec<_>>()
+            };
+            let first = lex4(&mut lexer);
+            assert_eq!(first[3].0, token::OpenDelim(token::Paren));
+
+            assert!(lexer.reset_to(BytePos(0)).is_ok());
+            assert_eq!(lex4(&mut lexer), first);
+
+            assert!(lexer.reset_to(BytePos(3)).is_ok());
+            let t = lexer.next_token();
+            assert_eq!((t.tok, t.sp), (mk_ident("f"), mk_sp(3, 4)));
+            assert_eq!(lexer.next_token().tok, token::OpenDelim(token::Paren));
+
+            // Positions outside the file are rejected, leaving the reader as it was.
+            assert!(lexer.reset_to(BytePos(14)).is_err());
+            assert_eq!(lexer.next_token().tok, mk_ident("x"));
+
+            // Resetting to the `\n` of a `\r\n` still sees the `\r` before it.
+            let mut lexer = setup_linting(&sm, &sh, "a\r\nb\r\n".to_string());
+            while lexer.next_token().tok != token::Eof {}
+            assert!(lexer.reset_to(BytePos(17)).is_ok());
+            lex_to_eof(lexer);
+            assert!(buffered_lints(&sh, BufferedEarlyLintId::MixedLineEndings).is_empty());
+        })
+    }
+
+    #[test]
+    fn reset_to_does_not_buffer_lints_again() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup_linting(&sm, &sh, "let x = 1; \nlet y = 2; \n".to_string());
+            while lexer.next_token().tok != token::Eof {}
+            let lints = buffered_lints(&sh, BufferedEarlyLintId::TrailingWhitespace);
+            assert_eq!(lints.len(), 2);
+
+            assert!(lexer.reset_to(BytePos(0)).is_ok());
+            while lexer.next_token().tok != token::Eof {}
+            assert!(lexer.reset_to(BytePos(12)).is_ok());
+            while lexer.next_token().tok != token::Eof {}
+            assert_eq!(sh.buffered_lints.borrow().len(), 2);
+            assert_eq!(buffered_lints(&sh, BufferedEarlyLintId::TrailingWhitespace), lints);
+        })
+    }
+
+    #[test]
+    fn mixed_indentation_in_raw_strings() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            lex_to_eof(setup_linting(&sm, &sh, "r#\"a\n\t\tb\n    c\n\"#".to_string()));
+            assert!(buffered_lints(&sh, BufferedEarlyLintId::MixedIndentationInRawStrings)
+                .is_empty());
+
+            lex_to_eof(setup_linting(&sm, &sh, "r#\"a\n\t b\n \tc\"#".to_string()));
+            let lints = buffered_lints(&sh, BufferedEarlyLintId::MixedIndentationInRawStrings);
+            assert_eq!(lints.len(), 1);
+            assert_eq!(lints[0].1, "raw string line is indented with both tabs and spaces");
+            assert_eq!(sm.span_to_snippet(lints[0].0).unwrap(), "\t ");
+        })
+    }
+
+    #[test]
+    fn mixed_line_endings() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lex_detecting = |src: &str| {
+                lex_to_eof(setup_linting(&sm, &sh, src.to_string()));
+                buffered_lints(&sh, BufferedEarlyLintId::MixedLineEndings)
+            };
+
+            assert!(lex_detecting("a\r\nb\r\n// c\r\n").is_empty());
+            assert!(lex_detecting("a\nb\n").is_empty());
+
+            let lints = lex_detecting("a\nb\r\nc\nd\r\n");
+            assert_eq!(lints.len(), 1);
+            assert_eq!(lints[0].1, "file uses mixed line endings");
+            assert_eq!(sm.span_to_snippet(lints[0].0).unwrap(), "\r\n");
+        })
+    }
+
+    #[test]
+    fn byte_raw_string_with_hash() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            assert_eq!(setup(&sm, &sh, "br#\"x\"#".to_string()).next_token().tok,
+                       token::Literal(token::ByteStrRaw(Symbol::intern("x"), 1), None));
+        })
+    }
+
+    #[test]
+    fn peek_is_ident() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let kw_fn = crate::symbol::keywords::Fn.name();
+            let mut lexer = setup(&sm, &sh, " fn r#fn fun".to_string());
+            assert!(!lexer.peek_is_ident(kw_fn));
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert!(lexer.peek_is_ident(kw_fn));
+            assert_eq!(lexer.next_token().tok, mk_ident("fn"));
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert!(!lexer.peek_is_ident(kw_fn));
+            assert_eq!(lexer.next_token().tok, token::Ident(Ident::from_str("fn"), true));
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert!(!lexer.peek_is_ident(kw_fn));
+            assert!(lexer.peek_is_ident(Symbol::intern("fun")));
+        })
+    }
+
+    #[test]
+    fn non_ascii_raw_byte_string() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let mut lexer = setup(&sm, &sh, "br\"caf\u{e9} \u{20ac}!\" x".to_string());
+            assert_eq!(lexer.next_token().tok,
+                       token::Literal(token::ByteStrRaw(Symbol::intern("caf? ?!"), 0), None));
+            assert_eq!(lexer.next_token().tok, token::Whitespace);
+            assert_eq!(lexer.next_token().tok, mk_ident("x"));
+            assert_eq!(sh.span_diagnostic.err_count(), 1);
+        })
+    }
+
+    #[test]
+    fn max_literal_suffix_len() {
+        with_globals(|| {
+            let output = Arc::new(Mutex::new(Vec::new()));
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let emitter = errors::emitter::EmitterWriter::new(
+                Box::new(Shared { data: output.clone() }),
+                Some(sm.clone()),
+                false,
+                false,
+                false,
+            );
+            let mut sh = mk_sess(sm.clone());
+            sh.span_diagnostic = errors::Handler::with_emitter(true, None, Box::new(emitter));
+            sh.max_literal_suffix_len = Some(10);
+
+            let suffix = "x".repeat(50);
+            let mut lexer = setup(&sm, &sh, format!("1{} 2short", suffix));
+            assert_eq!(lexer.next_token().tok,
+                       token::Literal(token::Integer(Symbol::intern("1")),
+                                      Some(Symbol::intern(&suffix))));
+            lex_to_eof(lexer);
+
+            let output = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+            let msg = format!("warning: literal suffix `{}` is suspiciously long", suffix);
+            assert_eq!(output.matches(&msg[..]).count(), 1);
+            assert!(!output.contains("`short`"));
+            assert_eq!(sh.span_diagnostic.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn line_indent() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lexer = setup(&sm, &sh, "fn f() {\n    let x;\n\t  y\n}".to_string());
+            assert_eq!(lexer.line_indent(BytePos(0)), 0);
+            assert_eq!(lexer.line_indent(BytePos(3)), 0);
+            assert_eq!(lexer.line_indent(BytePos(9)), 4);
+            assert_eq!(lexer.line_indent(BytePos(17)), 4);
+            assert_eq!(lexer.line_indent(BytePos(21)), 3);
+            assert_eq!(lexer.line_indent(BytePos(25)), 0);
+
+            // The second file starts at byte 27, after the first one.
+            let lexer = setup(&sm, &sh, "  é".to_string());
+            assert_eq!(lexer.line_indent(BytePos(29)), 2);
+            assert_eq!(lexer.line_indent(BytePos(30)), 0);
+        })
+    }
+
+    #[test]
+    fn odd_quotes_on_line() {
+        with_globals(|| {
+            let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+            let sh = mk_sess(sm.clone());
+            let lex_detecting = |src: &str| {
+                lex_to_eof(setup_linting(&sm, &sh, src.to_string()));
+                buffered_lints(&sh, BufferedEarlyLintId::OddQuotesOnLine)
+            };
+
+            assert!(lex_detecting("f(\"a\", '\"', \"b\\\"c\");\n").is_empty());
+            // quotes in comments and raw strings are not counted
+            assert!(lex_detecting("// \"\nr#\"\"#;\n").is_empty());
+
+            // The third file starts at byte 35, after the first two.
+            let lints = lex_detecting("f(\"a\", \"b\n\");\n");
+            let msg = "line has an odd number of `\"`; a string literal may be unterminated";
+            assert_eq!(lints, vec![(mk_sp(42, 43), msg.to_string()),
+                                   (mk_sp(45, 46), msg.to_string())]);
+        })
+    }
 }
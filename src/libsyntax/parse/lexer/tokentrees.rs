@@ -3,6 +3,8 @@ use crate::parse::lexer::{StringReader, UnmatchedBrace};
 use crate::parse::{token, PResult};
 use crate::tokenstream::{DelimSpan, IsJoint::*, TokenStream, TokenTree, TreeAndJoint};
 
+use std::cmp;
+
 impl<'a> StringReader<'a> {
     // Parse a stream of tokens into a list of `TokenTree`s, up to an `Eof`.
     crate fn parse_all_token_trees(&mut self) -> PResult<'a, TokenStream> {
@@ -73,6 +75,7 @@ impl<'a> StringReader<'a> {
 
                 // Parse the open delimiter.
                 self.open_braces.push((delim, self.span));
+                self.max_open_braces = cmp::max(self.max_open_braces, self.open_braces.len());
                 self.real_token();
 
                 // Parse the token trees within the delimiters.
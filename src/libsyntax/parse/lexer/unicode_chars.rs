@@ -104,8 +104,6 @@ const UNICODE_ARRAY: &[(char, &str, char)] = &[
     ('܂', "Syriac Sublinear Full Stop", '.'),
     ('꘎', "Vai Full Stop", '.'),
     ('𐩐', "Kharoshthi Punctuation Dot", '.'),
-    ('٠', "Arabic-Indic Digit Zero", '.'),
-    ('۰', "Extended Arabic-Indic Digit Zero", '.'),
     ('ꓸ', "Lisu Letter Tone Mya Ti", '.'),
     ('·', "Middle Dot", '.'),
     ('・', "Katakana Middle Dot", '.'),
@@ -293,7 +291,37 @@ const UNICODE_ARRAY: &[(char, &str, char)] = &[
     ('〉', "Right-Pointing Angle Bracket", '>'),
     ('〉', "Right Angle Bracket", '>'),
     ('》', "Right Double Angle Bracket", '>'),
-    ('＞', "Fullwidth Greater-Than Sign", '>'), ];
+    ('＞', "Fullwidth Greater-Than Sign", '>'),
+    ('０', "Fullwidth Digit Zero", '0'),
+    ('１', "Fullwidth Digit One", '1'),
+    ('２', "Fullwidth Digit Two", '2'),
+    ('３', "Fullwidth Digit Three", '3'),
+    ('４', "Fullwidth Digit Four", '4'),
+    ('５', "Fullwidth Digit Five", '5'),
+    ('６', "Fullwidth Digit Six", '6'),
+    ('７', "Fullwidth Digit Seven", '7'),
+    ('８', "Fullwidth Digit Eight", '8'),
+    ('９', "Fullwidth Digit Nine", '9'),
+    ('٠', "Arabic-Indic Digit Zero", '0'),
+    ('١', "Arabic-Indic Digit One", '1'),
+    ('٢', "Arabic-Indic Digit Two", '2'),
+    ('٣', "Arabic-Indic Digit Three", '3'),
+    ('٤', "Arabic-Indic Digit Four", '4'),
+    ('٥', "Arabic-Indic Digit Five", '5'),
+    ('٦', "Arabic-Indic Digit Six", '6'),
+    ('٧', "Arabic-Indic Digit Seven", '7'),
+    ('٨', "Arabic-Indic Digit Eight", '8'),
+    ('٩', "Arabic-Indic Digit Nine", '9'),
+    ('۰', "Extended Arabic-Indic Digit Zero", '0'),
+    ('۱', "Extended Arabic-Indic Digit One", '1'),
+    ('۲', "Extended Arabic-Indic Digit Two", '2'),
+    ('۳', "Extended Arabic-Indic Digit Three", '3'),
+    ('۴', "Extended Arabic-Indic Digit Four", '4'),
+    ('۵', "Extended Arabic-Indic Digit Five", '5'),
+    ('۶', "Extended Arabic-Indic Digit Six", '6'),
+    ('۷', "Extended Arabic-Indic Digit Seven", '7'),
+    ('۸', "Extended Arabic-Indic Digit Eight", '8'),
+    ('۹', "Extended Arabic-Indic Digit Nine", '9'), ];
 
 
 const ASCII_ARRAY: &[(char, &str)] = &[
@@ -321,7 +349,22 @@ const ASCII_ARRAY: &[(char, &str)] = &[
     ('+', "Plus Sign"),
     ('<', "Less-Than Sign"),
     ('=', "Equals Sign"),
-    ('>', "Greater-Than Sign"), ];
+    ('>', "Greater-Than Sign"),
+    ('0', "Digit Zero"),
+    ('1', "Digit One"),
+    ('2', "Digit Two"),
+    ('3', "Digit Three"),
+    ('4', "Digit Four"),
+    ('5', "Digit Five"),
+    ('6', "Digit Six"),
+    ('7', "Digit Seven"),
+    ('8', "Digit Eight"),
+    ('9', "Digit Nine"), ];
+
+/// Whether `ch` is a character that looks like an ASCII digit.
+crate fn is_confusable_digit(ch: char) -> bool {
+    UNICODE_ARRAY.iter().any(|&(c, _, ascii_char)| c == ch && ascii_char.is_ascii_digit())
+}
 
 crate fn check_for_substitution<'a>(reader: &StringReader<'a>,
                                   ch: char,
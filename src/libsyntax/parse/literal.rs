@@ -115,6 +115,14 @@ impl LitKind {
             token::ByteStrRaw(i, _) => {
                 LitKind::ByteStr(Lrc::new(i.to_string().into_bytes()))
             }
+            token::Frac(numerator, denominator) => {
+                err!(diag, |span, diag| {
+                    diag.struct_span_err(span, "rational literal is not supported")
+                        .span_label(span, "not supported")
+                        .emit();
+                });
+                LitKind::Err(Symbol::intern(&format!("{}/{}", numerator, denominator)))
+            }
         })
     }
 
@@ -359,6 +367,17 @@ fn float_lit(s: &str, suffix: Option<Symbol>, diag: Option<(Span, &Handler)>)
     debug!("float_lit: {:?}, {:?}", s, suffix);
     // FIXME #2252: bounds checking float literals is deferred until trans
 
+    // The lexer accepts hexadecimal floats like `0x1.8p3` for the benefit of
+    // tools, but they are not part of the language.
+    if s.starts_with("0x") {
+        err!(diag, |span, diag| {
+            diag.struct_span_err(span, "hexadecimal float literal is not supported")
+                .span_label(span, "not supported")
+                .emit();
+        });
+        return Some(LitKind::Err(Symbol::intern(s)));
+    }
+
     // Strip underscores without allocating a new String unless necessary.
     let s2;
     let s = if s.chars().any(|c| c == '_') {
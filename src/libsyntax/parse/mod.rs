@@ -51,6 +51,9 @@ pub struct ParseSess {
     /// operation token that followed it, but that the parser cannot identify without further
     /// analysis.
     pub ambiguous_block_expr_parse: Lock<FxHashMap<Span, Span>>,
+    /// If set, literal suffixes longer than this are warned about, as they
+    /// more likely come from a lexing mistake like a missing operator.
+    pub max_literal_suffix_len: Option<usize>,
 }
 
 impl ParseSess {
@@ -75,6 +78,7 @@ impl ParseSess {
             source_map,
             buffered_lints: Lock::new(vec![]),
             ambiguous_block_expr_parse: Lock::new(FxHashMap::default()),
+            max_literal_suffix_len: None,
         }
     }
 
@@ -2262,7 +2262,7 @@ impl<'a> Parser<'a> {
 
     /// Parses a single lifetime `'a` or panics.
     crate fn expect_lifetime(&mut self) -> Lifetime {
-        if let Some(ident) = self.token.lifetime() {
+        if let Some((ident, _)) = self.token.lifetime() {
             let span = self.span;
             self.bump();
             Lifetime { ident: Ident::new(ident.name, span), id: ast::DUMMY_NODE_ID }
@@ -2272,7 +2272,7 @@ impl<'a> Parser<'a> {
     }
 
     fn eat_label(&mut self) -> Option<Label> {
-        if let Some(ident) = self.token.lifetime() {
+        if let Some((ident, _)) = self.token.lifetime() {
             let span = self.span;
             self.bump();
             Some(Label { ident: Ident::new(ident.name, span) })
@@ -3188,7 +3188,8 @@ impl<'a> Parser<'a> {
                 // and lifetime tokens, so the former are never encountered during normal parsing.
                 match **nt {
                     token::NtIdent(ident, is_raw) => (token::Ident(ident, is_raw), ident.span),
-                    token::NtLifetime(ident) => (token::Lifetime(ident), ident.span),
+                    token::NtLifetime(ident, is_raw) =>
+                        (token::Lifetime(ident, is_raw), ident.span),
                     _ => return,
                 }
             }
@@ -4562,7 +4563,7 @@ impl<'a> Parser<'a> {
                 // Parse &pat / &mut pat
                 self.expect_and()?;
                 let mutbl = self.parse_mutability();
-                if let token::Lifetime(ident) = self.token {
+                if let token::Lifetime(ident, _) = self.token {
                     let mut err = self.fatal(&format!("unexpected lifetime `{}` in pattern",
                                                       ident));
                     err.span_label(self.span, "unexpected lifetime");
@@ -71,10 +71,13 @@ pub enum Lit {
     StrRaw(ast::Name, u16), /* raw str delimited by n hash symbols */
     ByteStr(ast::Name),
     ByteStrRaw(ast::Name, u16), /* raw byte str delimited by n hash symbols */
+    /// An experimental rational literal like `3/4r`, holding its numerator and
+    /// denominator. Only lexed by readers with rational literals enabled.
+    Frac(ast::Name, ast::Name),
 }
 
 #[cfg(target_arch = "x86_64")]
-static_assert!(MEM_SIZE_OF_LIT: mem::size_of::<Lit>() == 8);
+static_assert!(MEM_SIZE_OF_LIT: mem::size_of::<Lit>() == 12);
 
 impl Lit {
     crate fn literal_name(&self) -> &'static str {
@@ -86,7 +89,8 @@ impl Lit {
             Integer(_) => "integer literal",
             Float(_) => "float literal",
             Str_(_) | StrRaw(..) => "string literal",
-            ByteStr(_) | ByteStrRaw(..) => "byte string literal"
+            ByteStr(_) | ByteStrRaw(..) => "byte string literal",
+            Frac(..) => "rational literal",
         }
     }
 
@@ -197,7 +201,7 @@ pub enum Token {
 
     /* Name components */
     Ident(ast::Ident, /* is_raw */ bool),
-    Lifetime(ast::Ident),
+    Lifetime(ast::Ident, /* is_raw */ bool),
 
     Interpolated(Lrc<Nonterminal>),
 
@@ -220,7 +224,7 @@ pub enum Token {
 
 // `Token` is used a lot. Make sure it doesn't unintentionally get bigger.
 #[cfg(target_arch = "x86_64")]
-static_assert!(MEM_SIZE_OF_STATEMENT: mem::size_of::<Token>() == 16);
+static_assert!(MEM_SIZE_OF_STATEMENT: mem::size_of::<Token>() == 24);
 
 impl Token {
     /// Recovers a `Token` from an `ast::Ident`. This creates a raw identifier if necessary.
@@ -346,11 +350,11 @@ impl Token {
         }
     }
     /// Returns a lifetime identifier if this token is a lifetime.
-    pub fn lifetime(&self) -> Option<ast::Ident> {
+    pub fn lifetime(&self) -> Option<(ast::Ident, /* is_raw */ bool)> {
         match *self {
-            Lifetime(ident) => Some(ident),
+            Lifetime(ident, is_raw) => Some((ident, is_raw)),
             Interpolated(ref nt) => match **nt {
-                NtLifetime(ident) => Some(ident),
+                NtLifetime(ident, is_raw) => Some((ident, is_raw)),
                 _ => None,
             },
             _ => None,
@@ -490,12 +494,12 @@ impl Token {
                 _ => return None,
             },
             SingleQuote => match joint {
-                Ident(ident, false) => {
+                Ident(ident, is_raw) => {
                     let name = Symbol::intern(&format!("'{}", ident));
                     Lifetime(symbol::Ident {
                         name,
                         span: ident.span,
-                    })
+                    }, is_raw)
                 }
                 _ => return None,
             },
@@ -564,7 +568,7 @@ impl Token {
             (&DocComment(a), &DocComment(b)) |
             (&Shebang(a), &Shebang(b)) => a == b,
 
-            (&Lifetime(a), &Lifetime(b)) => a.name == b.name,
+            (&Lifetime(a, b), &Lifetime(c, d)) => b == d && a.name == c.name,
             (&Ident(a, b), &Ident(c, d)) => b == d && (a.name == c.name ||
                                                        a.name == keywords::DollarCrate.name() ||
                                                        c.name == keywords::DollarCrate.name()),
@@ -590,7 +594,7 @@ pub enum Nonterminal {
     NtExpr(P<ast::Expr>),
     NtTy(P<ast::Ty>),
     NtIdent(ast::Ident, /* is_raw */ bool),
-    NtLifetime(ast::Ident),
+    NtLifetime(ast::Ident, /* is_raw */ bool),
     NtLiteral(P<ast::Expr>),
     /// Stuff inside brackets for attributes
     NtMeta(ast::MetaItem),
@@ -610,7 +614,8 @@ impl PartialEq for Nonterminal {
         match (self, rhs) {
             (NtIdent(ident_lhs, is_raw_lhs), NtIdent(ident_rhs, is_raw_rhs)) =>
                 ident_lhs == ident_rhs && is_raw_lhs == is_raw_rhs,
-            (NtLifetime(ident_lhs), NtLifetime(ident_rhs)) => ident_lhs == ident_rhs,
+            (NtLifetime(ident_lhs, is_raw_lhs), NtLifetime(ident_rhs, is_raw_rhs)) =>
+                ident_lhs == ident_rhs && is_raw_lhs == is_raw_rhs,
             (NtTT(tt_lhs), NtTT(tt_rhs)) => tt_lhs == tt_rhs,
             // FIXME: Assume that all "complex" nonterminal are not equal, we can't compare them
             // correctly based on data from AST. This will prevent them from matching each other
@@ -672,8 +677,8 @@ impl Nonterminal {
                 let token = Token::Ident(ident, is_raw);
                 Some(TokenTree::Token(ident.span, token).into())
             }
-            Nonterminal::NtLifetime(ident) => {
-                let token = Token::Lifetime(ident);
+            Nonterminal::NtLifetime(ident, is_raw) => {
+                let token = Token::Lifetime(ident, is_raw);
                 Some(TokenTree::Token(ident.span, token).into())
             }
             Nonterminal::NtTT(ref tt) => {
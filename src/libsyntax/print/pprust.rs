@@ -178,6 +178,7 @@ pub fn literal_to_string(lit: token::Lit, suffix: Option<ast::Name>) -> String {
         token::ByteStrRaw(s, n)  => format!("br{delim}\"{string}\"{delim}",
                                             delim="#".repeat(n as usize),
                                             string=s),
+        token::Frac(n, d)        => format!("{}/{}r", n, d),
     };
 
     if let Some(suffix) = suffix {
@@ -235,7 +236,8 @@ pub fn token_to_string(tok: &Token) -> String {
         /* Name components */
         token::Ident(s, false)      => s.to_string(),
         token::Ident(s, true)       => format!("r#{}", s),
-        token::Lifetime(s)          => s.to_string(),
+        token::Lifetime(s, false)   => s.to_string(),
+        token::Lifetime(s, true)    => format!("'r#{}", s.without_first_quote()),
 
         /* Other */
         token::DocComment(s)        => s.to_string(),
@@ -260,7 +262,8 @@ pub fn nonterminal_to_string(nt: &Nonterminal) -> String {
         token::NtPat(ref e)         => pat_to_string(e),
         token::NtIdent(e, false)    => ident_to_string(e),
         token::NtIdent(e, true)     => format!("r#{}", ident_to_string(e)),
-        token::NtLifetime(e)        => ident_to_string(e),
+        token::NtLifetime(e, false) => ident_to_string(e),
+        token::NtLifetime(e, true)  => format!("'r#{}", e.without_first_quote()),
         token::NtLiteral(ref e)     => expr_to_string(e),
         token::NtTT(ref tree)       => tt_to_string(tree.clone()),
         token::NtImplItem(ref e)    => impl_item_to_string(e),
@@ -2061,7 +2064,7 @@ impl<'a> State<'a> {
             }
             ast::ExprKind::While(ref test, ref blk, opt_label) => {
                 if let Some(label) = opt_label {
-                    self.print_ident(label.ident)?;
+                    self.print_lifetime_ident(label.ident)?;
                     self.word_space(":")?;
                 }
                 self.head("while")?;
@@ -2071,7 +2074,7 @@ impl<'a> State<'a> {
             }
             ast::ExprKind::WhileLet(ref pats, ref expr, ref blk, opt_label) => {
                 if let Some(label) = opt_label {
-                    self.print_ident(label.ident)?;
+                    self.print_lifetime_ident(label.ident)?;
                     self.word_space(":")?;
                 }
                 self.head("while let")?;
@@ -2084,7 +2087,7 @@ impl<'a> State<'a> {
             }
             ast::ExprKind::ForLoop(ref pat, ref iter, ref blk, opt_label) => {
                 if let Some(label) = opt_label {
-                    self.print_ident(label.ident)?;
+                    self.print_lifetime_ident(label.ident)?;
                     self.word_space(":")?;
                 }
                 self.head("for")?;
@@ -2097,7 +2100,7 @@ impl<'a> State<'a> {
             }
             ast::ExprKind::Loop(ref blk, opt_label) => {
                 if let Some(label) = opt_label {
-                    self.print_ident(label.ident)?;
+                    self.print_lifetime_ident(label.ident)?;
                     self.word_space(":")?;
                 }
                 self.head("loop")?;
@@ -2135,7 +2138,7 @@ impl<'a> State<'a> {
             }
             ast::ExprKind::Block(ref blk, opt_label) => {
                 if let Some(label) = opt_label {
-                    self.print_ident(label.ident)?;
+                    self.print_lifetime_ident(label.ident)?;
                     self.word_space(":")?;
                 }
                 // containing cbox, will be closed by print-block at }
@@ -2219,7 +2222,7 @@ impl<'a> State<'a> {
                 self.s.word("break")?;
                 self.s.space()?;
                 if let Some(label) = opt_label {
-                    self.print_ident(label.ident)?;
+                    self.print_lifetime_ident(label.ident)?;
                     self.s.space()?;
                 }
                 if let Some(ref expr) = *opt_expr {
@@ -2231,7 +2234,7 @@ impl<'a> State<'a> {
                 self.s.word("continue")?;
                 self.s.space()?;
                 if let Some(label) = opt_label {
-                    self.print_ident(label.ident)?;
+                    self.print_lifetime_ident(label.ident)?;
                     self.s.space()?
                 }
             }
@@ -2671,7 +2674,7 @@ impl<'a> State<'a> {
         match arm.body.node {
             ast::ExprKind::Block(ref blk, opt_label) => {
                 if let Some(label) = opt_label {
-                    self.print_ident(label.ident)?;
+                    self.print_lifetime_ident(label.ident)?;
                     self.word_space(":")?;
                 }
 
@@ -2815,7 +2818,18 @@ impl<'a> State<'a> {
     }
 
     pub fn print_lifetime(&mut self, lifetime: ast::Lifetime) -> io::Result<()> {
-        self.print_name(lifetime.ident.name)
+        self.print_lifetime_ident(lifetime.ident)
+    }
+
+    /// Prints the name of a lifetime or label. A keyword name, which can only
+    /// have been written as a raw lifetime, is printed as one, like `'r#fn`.
+    fn print_lifetime_ident(&mut self, ident: ast::Ident) -> io::Result<()> {
+        let name = ident.without_first_quote();
+        if ident.name != keywords::StaticLifetime.name() && name.is_raw_guess() {
+            self.s.word(format!("'r#{}", name))
+        } else {
+            self.print_name(ident.name)
+        }
     }
 
     pub fn print_lifetime_bounds(&mut self, lifetime: ast::Lifetime, bounds: &ast::GenericBounds)
@@ -3146,6 +3160,7 @@ mod tests {
 
     use crate::ast;
     use crate::source_map;
+    use crate::symbol::Symbol;
     use crate::with_globals;
     use syntax_pos;
 
@@ -3195,4 +3210,17 @@ mod tests {
             assert_eq!(varstr, "principal_skinner");
         })
     }
+
+    #[test]
+    fn test_token_to_string() {
+        with_globals(|| {
+            assert_eq!(token_to_string(&token::BinOp(token::Plus)), "+");
+            assert_eq!(token_to_string(&token::FatArrow), "=>");
+            let ident = ast::Ident::from_str("foo");
+            assert_eq!(token_to_string(&token::Ident(ident, false)), "foo");
+            assert_eq!(token_to_string(&token::Ident(ident, true)), "r#foo");
+            let lit = token::Literal(token::Str_(Symbol::intern("bar")), None);
+            assert_eq!(token_to_string(&lit), "\"bar\"");
+        })
+    }
 }
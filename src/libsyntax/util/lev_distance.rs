@@ -132,5 +132,21 @@ fn test_find_best_match_for_name() {
             find_best_match_for_name(input.iter(), "aaaa", Some(4)),
             Some(Symbol::intern("AAAA"))
         );
+
+        // The closest of several identifiers within the limit is chosen
+        let input = vec![Symbol::intern("len"), Symbol::intern("length"),
+                         Symbol::intern("capacity")];
+        assert_eq!(
+            find_best_match_for_name(input.iter(), "lenght", None),
+            Some(Symbol::intern("length"))
+        );
+        assert_eq!(
+            find_best_match_for_name(input.iter(), "capcity", None),
+            Some(Symbol::intern("capacity"))
+        );
+        assert_eq!(
+            find_best_match_for_name(input.iter(), "reserve", None),
+            None
+        );
     })
 }
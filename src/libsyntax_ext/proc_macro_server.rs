@@ -145,9 +145,9 @@ impl FromInternal<(TreeAndJoint, &'_ ParseSess, &'_ mut Vec<Self>)>
             Ident(ident, false) if ident.name == keywords::DollarCrate.name() =>
                 tt!(Ident::dollar_crate()),
             Ident(ident, is_raw) => tt!(Ident::new(ident.name, is_raw)),
-            Lifetime(ident) => {
+            Lifetime(ident, is_raw) => {
                 let ident = ident.without_first_quote();
-                stack.push(tt!(Ident::new(ident.name, false)));
+                stack.push(tt!(Ident::new(ident.name, is_raw)));
                 tt!(Punct::new('\'', true))
             }
             Literal(lit, suffix) => tt!(Literal { lit, suffix }),
@@ -431,6 +431,7 @@ symbols! {
         RangeTo,
         RangeToInclusive,
         raw_identifiers,
+        raw_lifetimes,
         Ready,
         reason,
         recursion_limit,
@@ -584,6 +585,7 @@ symbols! {
         unsized_tuple_coercion,
         unstable,
         untagged_unions,
+        unused_type_alias_params,
         unwind,
         unwind_attributes,
         used,
@@ -0,0 +1,7 @@
+// pp-exact
+
+#![feature(raw_lifetimes)]
+
+fn f<'r#fn>(x: &'r#fn u8) -> &'r#fn u8 { x }
+
+fn main() { 'r#loop: loop { break 'r#loop ; } }
@@ -0,0 +1,19 @@
+// Test that a `&mut T` argument is accepted where a function expects `&T`,
+// and that the value is still usable mutably after the call.
+
+fn total(v: &Vec<u32>) -> u32 {
+    v.iter().sum()
+}
+
+fn first<T>(v: &[T]) -> &T {
+    &v[0]
+}
+
+fn main() {
+    let mut v = vec![1, 2, 3];
+    let r = &mut v;
+    assert_eq!(total(r), 6);
+    r.push(4);
+    assert_eq!(total(r), 10);
+    assert_eq!(*first(&mut v), 1);
+}
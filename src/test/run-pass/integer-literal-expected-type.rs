@@ -0,0 +1,16 @@
+// Test that an unsuffixed integer literal takes its type from the expected
+// type, and falls back to `i32` when nothing constrains it.
+
+use std::mem::size_of_val;
+
+fn main() {
+    let x: u8 = 5;
+    assert_eq!(size_of_val(&x), 1);
+
+    let y = 5;
+    assert_eq!(size_of_val(&y), 4);
+
+    // Only valid if the literal is typed as `i64` rather than `i32`.
+    let z: i64 = 1 << 40;
+    assert_eq!(z, 1_099_511_627_776);
+}
@@ -0,0 +1,33 @@
+// Test that comparison operators on a user type resolve to its `PartialEq` and
+// `PartialOrd` methods and that the comparison has type `bool`.
+
+use std::cmp::Ordering;
+
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.major == other.major && self.minor == other.minor
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some((self.major, self.minor).cmp(&(other.major, other.minor)))
+    }
+}
+
+pub fn main() {
+    let a = Version { major: 1, minor: 2 };
+    let b = Version { major: 1, minor: 3 };
+
+    let same: bool = a == b;
+    let older: bool = a < b;
+    assert!(!same);
+    assert!(older);
+    assert!(a != b);
+    assert!(b >= a);
+}
@@ -0,0 +1,27 @@
+// run-pass
+#![feature(raw_lifetimes)]
+
+struct S<'r#impl>(&'r#impl u8);
+
+fn r#fn<'r#fn>(x: &'r#fn u8) -> &'r#fn u8 {
+    x
+}
+
+macro_rules! id {
+    ($l:lifetime) => { $l: loop { break $l; } };
+}
+
+pub fn main() {
+    let x = 1;
+    assert_eq!(1, *r#fn(&x));
+    assert_eq!(1, *S(&x).0);
+
+    let mut n = 0;
+    'r#while: while n < 3 {
+        n += 1;
+        continue 'r#while;
+    }
+    assert_eq!(3, n);
+
+    id!('r#match);
+}
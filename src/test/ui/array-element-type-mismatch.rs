@@ -0,0 +1,8 @@
+// Check that an element of an array literal is checked against the element type of the
+// expected array type, so the error points at the mismatched element.
+
+fn main() {
+    let _v: [i32; 3] = [1, "x", 3];
+    //~^ ERROR mismatched types
+    //~| expected i32, found reference
+}
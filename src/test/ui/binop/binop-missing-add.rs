@@ -0,0 +1,6 @@
+struct Meters(u32);
+
+fn main() {
+    let total = Meters(1) + Meters(2);
+    //~^ ERROR binary operation `+` cannot be applied to type `Meters`
+}
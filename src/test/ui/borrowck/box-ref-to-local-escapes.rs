@@ -0,0 +1,17 @@
+// Check that boxing a reference to a local is accepted, and that the box is rejected
+// once it would outlive the local.
+
+fn boxed_in_scope() -> u32 {
+    let x = 22;
+    let boxed = Box::new(&x);
+    **boxed
+}
+
+fn boxed_escaping<'a>() -> Box<&'a u32> {
+    let x = 22;
+    let boxed = Box::new(&x);
+    boxed
+    //~^ ERROR cannot return value referencing local variable `x`
+}
+
+fn main() {}
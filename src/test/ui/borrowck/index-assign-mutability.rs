@@ -0,0 +1,10 @@
+// Check that assigning through an index requires a mutable container.
+
+fn main() {
+    let mut mutable = vec![1, 2, 3];
+    mutable[0] = 4;
+
+    let immutable = vec![1, 2, 3];
+    immutable[0] = 4;
+    //~^ ERROR cannot borrow `immutable` as mutable, as it is not declared as mutable
+}
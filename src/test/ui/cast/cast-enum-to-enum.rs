@@ -0,0 +1,14 @@
+enum Fruit {
+    Apple,
+    Pear,
+}
+
+enum Color {
+    Red,
+    Green,
+}
+
+fn main() {
+    let c = Fruit::Apple as Color;
+    //~^ ERROR cannot cast between enum types: `Fruit` as `Color`
+}
@@ -0,0 +1,22 @@
+// Casting between a number and a tuple struct wrapping one suggests going
+// through the wrapped value, while plain scalar casts keep working.
+
+struct Meters(u8);
+struct Big(u32);
+struct Wrapper<T>(T);
+
+enum Level { Low, High }
+
+fn main() {
+    let _ = 1u8 as f64;
+    let _ = Level::Low as isize;
+    let _ = Level::High as isize;
+    let m = Meters(5);
+    let _ = m as u8; //~ ERROR non-primitive cast
+    let _ = Big(1) as u8; //~ ERROR non-primitive cast
+    let _ = 5u8 as Meters; //~ ERROR non-primitive cast
+    let _ = 5u8 as Big; //~ ERROR non-primitive cast
+    let _ = 5u8 as Wrapper<u8>; //~ ERROR non-primitive cast
+    let _ = Meters(5) as *const u8; //~ ERROR non-primitive cast
+    let _ = () as i32; //~ ERROR non-primitive cast
+}
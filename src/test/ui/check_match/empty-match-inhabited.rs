@@ -0,0 +1,14 @@
+// A match with no arms is only accepted when the scrutinee's type has no
+// values.
+
+enum Void {}
+
+fn absurd(v: Void) -> u32 {
+    match v {}
+}
+
+fn int(x: i32) -> u32 {
+    match x {} //~ ERROR non-exhaustive patterns: type `i32` is non-empty
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// compile-pass
+
+// Check that a wildcard arm after arms covering every variant of an enum is reported.
+
+#![warn(unreachable_patterns)]
+
+enum Direction {
+    Left,
+    Right,
+}
+
+fn main() {
+    match Direction::Left {
+        Direction::Left => {}
+        Direction::Right => {}
+        _ => {} //~ WARN unreachable pattern
+    }
+}
@@ -0,0 +1,11 @@
+fn main() {
+    let tail = || -> u32 { "not a number" };
+    //~^ ERROR mismatched types
+    let early = |x: bool| -> u32 {
+        if x {
+            return "not a number";
+            //~^ ERROR mismatched types
+        }
+        0
+    };
+}
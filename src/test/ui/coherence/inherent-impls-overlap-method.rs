@@ -0,0 +1,14 @@
+// Check that overlapping inherent impls providing the same method are reported as
+// conflicting definitions before any call to the method is resolved.
+
+struct Wrapper<T>(T);
+
+impl<T> Wrapper<T> {
+    fn get(&self) -> u8 { 0 } //~ ERROR duplicate definitions with name `get`
+}
+
+impl Wrapper<u8> {
+    fn get(&self) -> u8 { self.0 }
+}
+
+fn main() {}
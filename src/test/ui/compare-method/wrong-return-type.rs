@@ -0,0 +1,15 @@
+// Check that an impl method whose return type differs from the trait points at the
+// return types rather than at the whole signature.
+
+trait Shape {
+    fn area(&self, scale: f64) -> f64;
+}
+
+struct Square;
+
+impl Shape for Square {
+    fn area(&self, scale: f64) -> u32 { 0 }
+    //~^ ERROR method `area` has an incompatible type for trait
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+// Check that constant indices into a fixed-size array are bounds-checked at compile time,
+// while in-bounds and non-constant indices are accepted.
+
+fn main() {
+    let array = [1, 2, 3];
+    let _in_bounds = array[2];
+    let _runtime = array[std::env::args().len()];
+    let _out_of_bounds = array[5]; //~ ERROR index out of bounds: the len is 3 but the index is 5
+}
@@ -0,0 +1,20 @@
+// Calls to non-const functions and methods are rejected at the call site
+// when they appear in a constant initializer.
+
+#![allow(const_err)]
+
+struct Counter;
+
+impl Counter {
+    fn get(&self) -> u32 { 1 }
+}
+
+fn answer() -> u32 { 42 }
+
+const A: u32 = answer();
+//~^ ERROR calls in constants are limited to constant functions
+
+const B: u32 = 1 + Counter.get();
+//~^ ERROR calls in constants are limited to constant functions
+
+fn main() {}
@@ -0,0 +1,9 @@
+// Check that a string discriminant is rejected as a type mismatch at the expression.
+
+enum Animal {
+    Cat = "cat",
+    //~^ ERROR mismatched types
+    //~| expected isize, found reference
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// check that raw lifetimes and labels are feature-gated
+
+fn f<'r#fn>(x: &'r#fn u8) -> &'r#fn u8 { x }
+//~^ ERROR raw lifetimes and labels are experimental
+//~| ERROR raw lifetimes and labels are experimental
+//~| ERROR raw lifetimes and labels are experimental
+
+fn main() {
+    'r#loop: loop { break 'r#loop; }
+    //~^ ERROR raw lifetimes and labels are experimental
+    //~| ERROR raw lifetimes and labels are experimental
+}
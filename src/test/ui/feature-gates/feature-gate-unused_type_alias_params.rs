@@ -0,0 +1,5 @@
+// check that unused type alias parameters are errors without the feature
+
+type Pair<A, B> = Box<A>; //~ ERROR type parameter `B` is unused
+
+fn main() {}
@@ -0,0 +1,8 @@
+// Autoderef on a method call stops at a type that hasn't been inferred yet,
+// even when it sits behind a reference, and asks for an annotation.
+
+fn main() {
+    let x = panic!();
+    let r = &x;
+    r.len(); //~ ERROR type annotations needed
+}
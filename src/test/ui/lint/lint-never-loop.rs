@@ -0,0 +1,30 @@
+#![deny(never_loop)]
+
+fn main() {
+    loop {
+        break;
+    }
+    //~^^^ ERROR this loop runs at most once
+
+    let mut n = 0;
+    loop {
+        n += 1;
+        if n == 3 {
+            break;
+        }
+    }
+
+    while n > 0 {
+        n -= 1;
+        break;
+    }
+    //~^^^^ ERROR this loop runs at most once
+
+    loop {
+        if n < 3 {
+            n += 1;
+            continue;
+        }
+        break;
+    }
+}
@@ -0,0 +1,17 @@
+// compile-pass
+
+// Function names and local bindings that aren't snake case are linted by
+// default, with the converted name as the suggestion.
+
+fn doThing() {}
+//~^ WARN function `doThing` should have a snake case name
+
+fn do_thing() {}
+
+fn main() {
+    let itemCount = 1;
+    //~^ WARN variable `itemCount` should have a snake case name
+    let item_count = itemCount;
+    doThing();
+    do_thing();
+}
@@ -0,0 +1,16 @@
+// compile-pass
+
+// Check that a `while` condition comparing an unsigned value with zero is linted,
+// and that a comparison which can be false is not.
+
+#![warn(unused_comparisons)]
+
+fn main() {
+    let mut x: u32 = 3;
+    while x < 0 { //~ WARN comparison is useless due to type limits
+        x -= 1;
+    }
+    while x < 1 {
+        x += 1;
+    }
+}
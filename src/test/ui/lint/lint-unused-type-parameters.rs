@@ -0,0 +1,25 @@
+// edition:2018
+
+#![feature(async_await)]
+#![deny(unused_type_parameters)]
+#![allow(dead_code)]
+
+fn unused<T>() {}
+//~^ ERROR type parameter `T` is never used
+
+fn in_signature<T>(t: T) -> T { t }
+
+fn in_body<T>() -> usize {
+    std::mem::size_of::<T>()
+}
+
+fn in_bound<T, U: From<T>>(u: U) -> U { u }
+
+fn with_impl_trait<T>(_: impl Sized) {}
+//~^ ERROR type parameter `T` is never used
+
+fn in_impl_trait_return<T>() -> impl Fn(T) -> T { |t| t }
+
+async fn in_async_return<T>() -> Vec<T> { vec![] }
+
+fn main() {}
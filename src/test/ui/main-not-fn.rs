@@ -0,0 +1,9 @@
+// error-pattern: `main` function not found
+// Check that a `main` that holds a function pointer instead of being a `fn` item gets a
+// missing `main` error rather than an ICE.
+
+#![allow(non_upper_case_globals)]
+
+fn run() {}
+
+static main: fn() = run;
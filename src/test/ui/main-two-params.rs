@@ -0,0 +1,2 @@
+fn main(argc: isize, argv: *const *const u8) { }
+//~^ ERROR main function has wrong type [E0580]
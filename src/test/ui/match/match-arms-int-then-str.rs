@@ -0,0 +1,16 @@
+// The first arm establishes the type the later arms must match, and the
+// error points back at it.
+
+fn describe(n: i32) {
+    let _x = match n { //~ NOTE `match` arms have incompatible types
+        0 => n, //~ NOTE this is found to be of type `i32`
+        _ => "many",
+        //~^ ERROR match arms have incompatible types
+        //~| NOTE expected i32, found reference
+        //~| NOTE expected type `i32`
+    };
+}
+
+fn main() {
+    describe(3);
+}
@@ -0,0 +1,19 @@
+// Test that ambiguous method candidates point at the glob imports that
+// brought each trait into scope.
+
+mod a {
+    pub trait Foo { fn foo(&self) {} }
+    impl Foo for u8 {}
+}
+
+mod b {
+    pub trait Bar { fn foo(&self) {} }
+    impl Bar for u8 {}
+}
+
+use a::*;
+use b::*;
+
+fn main() {
+    1u8.foo(); //~ ERROR multiple applicable items in scope
+}
@@ -0,0 +1,17 @@
+// Check that using a method as a value suggests calling it.
+
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn get(&self) -> u32 {
+        self.count
+    }
+}
+
+fn main() {
+    let counter = Counter { count: 0 };
+    let _value: u32 = counter.get;
+    //~^ ERROR attempted to take value of method `get` on type `Counter`
+}
@@ -0,0 +1,14 @@
+// `mem::swap` takes both operands as `&mut T`, so swapping values of
+// different types is a type mismatch.
+
+use std::mem;
+
+fn main() {
+    let mut a = 1;
+    let mut b = 2;
+    mem::swap(&mut a, &mut b);
+
+    let mut n: i32 = 3;
+    let mut s = String::new();
+    mem::swap(&mut n, &mut s); //~ ERROR mismatched types
+}
@@ -0,0 +1,12 @@
+// Check that a tuple expression checked against a struct type is reported at the tuple.
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    let _p: Point = (1, 2);
+    //~^ ERROR mismatched types
+    //~| expected struct `Point`, found tuple
+}
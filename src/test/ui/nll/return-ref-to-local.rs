@@ -0,0 +1,11 @@
+// Check that returning a reference to a local variable is reported at the reference.
+
+#![feature(nll)]
+
+fn local_ref<'a>() -> &'a u32 {
+    let x = 22;
+    &x
+    //~^ ERROR cannot return reference to local variable `x` [E0515]
+}
+
+fn main() {}
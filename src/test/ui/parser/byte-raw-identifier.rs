@@ -0,0 +1,4 @@
+fn main() {
+    let _ = br#foo;
+    //~^ ERROR byte raw identifiers are not valid
+}
@@ -0,0 +1,10 @@
+// compile-flags: -Z continue-parse-after-error
+
+fn main() {
+    let _ = 1e_5;
+    let _ = 1e5_;
+    let _ = 1e_;
+    //~^ ERROR exponent requires at least one digit, found only separators
+    let _ = 2.5E-__;
+    //~^ ERROR exponent requires at least one digit, found only separators
+}
@@ -10,5 +10,5 @@ fn main() {
     let _ = b'/';
 
     let _ = ' hello // here's a comment
-    //~^ ERROR: unterminated character literal
+    //~^ ERROR: lone `'` in source
 }
@@ -0,0 +1,13 @@
+pub fn main() {
+    let c1 = '\u{1F600}';
+    let c2 = '\u{1f600}';
+    let s = "\u{1F600}\u{1f600}";
+    let c = '\u{1F600';
+    //~^ ERROR unterminated unicode escape (needed a `}`)
+    let s = "\u{1F600";
+    //~^ ERROR unterminated unicode escape (needed a `}`)
+    let b = b'\u{7F';
+    //~^ ERROR unterminated unicode escape (needed a `}`)
+    let bs = b"\u{7F";
+    //~^ ERROR unterminated unicode escape (needed a `}`)
+}
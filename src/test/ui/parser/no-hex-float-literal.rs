@@ -6,4 +6,6 @@ fn main() {
     0xDEAD.BEEFp-2f;
     //~^ ERROR invalid suffix `f` for float literal
     //~| ERROR `{integer}` is a primitive type and therefore doesn't have fields
+    0x10px;
+    //~^ ERROR invalid suffix `px` for numeric literal
 }
@@ -0,0 +1,6 @@
+// compile-flags: -Z continue-parse-after-error
+
+pub fn main() {
+    br"café €";
+    //~^ ERROR raw byte string must be ASCII
+}
@@ -0,0 +1,6 @@
+// `_` is a placeholder, not a value, and is rejected where an expression
+// is expected.
+
+fn main() {
+    let x = _; //~ ERROR expected expression, found reserved identifier `_`
+}
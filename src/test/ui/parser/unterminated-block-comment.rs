@@ -0,0 +1,5 @@
+fn main() {}
+
+/* this comment
+is never closed
+//~^^ ERROR unterminated block comment
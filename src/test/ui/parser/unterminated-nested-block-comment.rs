@@ -0,0 +1,6 @@
+fn main() {}
+
+/* outer
+   /* inner */
+   /* still open
+//~^ ERROR unterminated block comment
@@ -0,0 +1,22 @@
+// Check that box and reference patterns name both pointer kinds when they do not match
+// the value being matched.
+
+#![feature(box_patterns)]
+
+fn main() {
+    let boxed: Box<u8> = Box::new(0);
+    match boxed {
+        &0 => {}
+        //~^ ERROR mismatched types
+        //~| expected struct `std::boxed::Box`, found reference
+        _ => {}
+    }
+
+    let reference: &u8 = &0;
+    match reference {
+        box 0 => {}
+        //~^ ERROR mismatched types
+        //~| expected u8, found struct `std::boxed::Box`
+        _ => {}
+    }
+}
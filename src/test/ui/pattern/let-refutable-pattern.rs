@@ -0,0 +1,12 @@
+// A `let` pattern has to match every value of its type. Destructuring a
+// tuple is fine, binding through `Some` is not.
+
+fn first(opt: Option<u32>, pair: (u32, u32)) -> u32 {
+    let (a, _) = pair;
+    let Some(x) = opt; //~ ERROR refutable pattern in local binding: `None` not covered
+    a + x
+}
+
+fn main() {
+    first(Some(1), (2, 3));
+}
@@ -0,0 +1,26 @@
+// A `pub` method can be called from another module, a private one only
+// from within the module that defines it.
+
+mod shapes {
+    pub struct Square(pub u32);
+
+    impl Square {
+        pub fn area(&self) -> u32 { self.side() * self.side() }
+        fn side(&self) -> u32 { self.0 }
+    }
+}
+
+mod report {
+    use shapes::Square;
+
+    pub fn describe(s: &Square) -> u32 {
+        s.area() + s.side() //~ ERROR method `side` is private
+    }
+}
+
+fn main() {
+    let s = shapes::Square(3);
+    s.area();
+    s.side(); //~ ERROR method `side` is private
+    report::describe(&s);
+}
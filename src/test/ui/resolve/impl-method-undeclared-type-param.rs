@@ -0,0 +1,21 @@
+// Type parameters used in a method of a generic impl must be declared on the
+// impl or on the method itself.
+
+struct Map<Key>(Vec<Key>);
+
+impl<Key> Map<Key> {
+    fn first(&self) -> Option<&Key> {
+        self.0.first()
+    }
+
+    fn value(&self, key: &Key) -> Value {
+        //~^ ERROR cannot find type `Value` in this scope
+        loop {}
+    }
+
+    fn declared<Value>(&self, key: &Key) -> Option<Value> {
+        None
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+// An impl whose self type names a type parameter the impl never declares
+// is reported during resolution rather than ICEing in type checking.
+
+struct Wrapper<T>(T);
+
+impl Wrapper<T> {
+    //~^ ERROR cannot find type `T` in this scope
+    fn get(&self) {}
+}
+
+fn main() {}
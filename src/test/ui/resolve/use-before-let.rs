@@ -0,0 +1,7 @@
+// Check that a local used before its `let` in the same block is not in scope there.
+
+fn main() {
+    let doubled = counter * 2;
+    //~^ ERROR cannot find value `counter` in this scope
+    let counter = 21;
+}
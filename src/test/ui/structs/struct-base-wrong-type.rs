@@ -12,3 +12,10 @@ fn main() {
     let f = Foo { a: 2, ..b };        //~ ERROR mismatched types
     let f__isize = Foo { a: 2, ..4 }; //~ ERROR mismatched types
 }
+
+// The base must have the type being built, even if another struct has the same fields.
+struct Baz { a: isize, b: isize }
+
+fn same_fields(baz: Baz) -> Foo {
+    Foo { a: 2, ..baz } //~ ERROR mismatched types
+}
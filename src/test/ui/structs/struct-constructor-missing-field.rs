@@ -0,0 +1,15 @@
+// Check that a constructor function leaving a field out of its struct expression is rejected.
+
+struct Counter {
+    count: u32,
+    step: u32,
+}
+
+impl Counter {
+    fn new() -> Counter {
+        Counter { count: 0 }
+        //~^ ERROR missing field `step` in initializer of `Counter`
+    }
+}
+
+fn main() {}
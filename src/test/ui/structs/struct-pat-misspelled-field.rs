@@ -0,0 +1,12 @@
+// Check that a misspelled field in a struct pattern suggests the field it most likely means.
+
+struct Person {
+    name: String,
+    age: u32,
+}
+
+fn main() {
+    let p = Person { name: String::new(), age: 0 };
+    let Person { nmae, age } = p;
+    //~^ ERROR struct `Person` does not have a field named `nmae`
+}
@@ -0,0 +1,16 @@
+// Every unknown field in a struct update expression is reported, not just
+// the first one.
+
+struct Config {
+    width: u32,
+    height: u32,
+}
+
+fn main() {
+    let base = Config { width: 1, height: 2 };
+    let _ = Config {
+        colour: 3, //~ ERROR struct `Config` has no field named `colour`
+        depth: 4, //~ ERROR struct `Config` has no field named `depth`
+        ..base
+    };
+}
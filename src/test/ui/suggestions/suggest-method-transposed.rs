@@ -0,0 +1,13 @@
+// A method name with two letters swapped still gets a similar-name
+// suggestion.
+
+struct Rope;
+
+impl Rope {
+    fn length(&self) -> usize { 0 }
+}
+
+fn main() {
+    let r = Rope;
+    let _ = r.lenght(); //~ ERROR no method named `lenght` found
+}
@@ -0,0 +1,14 @@
+// Tuple indexing into a struct with named fields points out that the type is
+// not a tuple, and suggests a field whose name ends in the same number.
+
+struct Pair { _0: u8, _1: u8 }
+struct Named { first: u8 }
+
+fn main() {
+    let pair = Pair { _0: 1, _1: 2 };
+    pair.1;
+    //~^ ERROR no field `1` on type `Pair`
+    let named = Named { first: 1 };
+    named.0;
+    //~^ ERROR no field `0` on type `Named`
+}
@@ -9,5 +9,5 @@ impl E {
 }
 
 fn main() {
-    <E>::V(); //~ ERROR this function takes 1 parameter but 0 parameters were supplied
+    <E>::V(); //~ ERROR this enum variant `V` takes 1 parameter but 0 parameters were supplied
 }
@@ -0,0 +1,11 @@
+// compile-pass
+
+// With the feature enabled, unused type alias parameters are reported as warnings.
+
+#![feature(unused_type_alias_params)]
+
+type Pair<A, B> = Box<A>; //~ WARN type parameter `B` is unused
+
+fn main() {
+    let _: Pair<u8, ()> = Box::new(0);
+}
@@ -0,0 +1,24 @@
+// Check that arity mismatches list the expected types, and that method calls also point at
+// where the missing arguments would go.
+
+fn f(a: u16, b: &str) {}
+//~^ NOTE defined here
+
+struct S;
+
+impl S {
+    fn m(&self, a: u16, b: &str) {}
+    //~^ NOTE defined here
+}
+
+fn main() {
+    f(0);
+    //~^ ERROR this function takes 2 parameters but 1 parameter was supplied
+    //~| NOTE expected 2 parameters
+    //~| NOTE expected parameter types: `u16`, `&str`
+    S.m();
+    //~^ ERROR this function takes 2 parameters but 0 parameters were supplied
+    //~| NOTE expected 2 parameters
+    //~| NOTE missing 2 arguments
+    //~| NOTE expected parameter types: `u16`, `&str`
+}
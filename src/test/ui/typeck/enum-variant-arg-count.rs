@@ -0,0 +1,10 @@
+enum E {
+    Pair(u8, u8),
+}
+
+fn main() {
+    let _ = E::Pair(1);
+    //~^ ERROR this enum variant `Pair` takes 2 parameters but 1 parameter was supplied
+    let _ = Some(1, 2);
+    //~^ ERROR this enum variant `Some` takes 1 parameter but 2 parameters were supplied
+}